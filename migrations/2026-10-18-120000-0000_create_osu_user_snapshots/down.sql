@@ -0,0 +1,3 @@
+DROP TABLE osu_user_snapshots;
+
+ALTER TABLE osu_users DROP COLUMN playcount;
@@ -0,0 +1,16 @@
+ALTER TABLE osu_users ADD COLUMN playcount INTEGER NOT NULL DEFAULT 0;
+
+CREATE TABLE IF NOT EXISTS osu_user_snapshots (
+  id BIGSERIAL PRIMARY KEY,
+  osu_id BIGINT NOT NULL,
+  mode VARCHAR(7) NOT NULL,
+  pp FLOAT NOT NULL,
+  accuracy FLOAT NOT NULL,
+  global_rank INTEGER NOT NULL,
+  country_rank INTEGER NOT NULL,
+  playcount INTEGER NOT NULL,
+  ranked_score BIGINT NOT NULL,
+  time_taken TIMESTAMPTZ DEFAULT now() NOT NULL
+);
+
+CREATE INDEX osu_user_snapshots_osu_id_mode_time_taken_idx ON osu_user_snapshots (osu_id, mode, time_taken);
@@ -0,0 +1,7 @@
+DROP INDEX beatmaps_mode_difficulty_rating_idx;
+DROP INDEX beatmapsets_creator_trgm_idx;
+DROP INDEX beatmapsets_title_trgm_idx;
+DROP INDEX beatmapsets_artist_trgm_idx;
+DROP INDEX beatmaps_version_trgm_idx;
+
+ALTER TABLE beatmaps DROP COLUMN od;
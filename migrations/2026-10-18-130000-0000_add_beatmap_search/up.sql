@@ -0,0 +1,9 @@
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+ALTER TABLE beatmaps ADD COLUMN od FLOAT8;
+
+CREATE INDEX beatmaps_version_trgm_idx ON beatmaps USING gin (version gin_trgm_ops);
+CREATE INDEX beatmapsets_artist_trgm_idx ON beatmapsets USING gin (artist gin_trgm_ops);
+CREATE INDEX beatmapsets_title_trgm_idx ON beatmapsets USING gin (title gin_trgm_ops);
+CREATE INDEX beatmapsets_creator_trgm_idx ON beatmapsets USING gin (creator gin_trgm_ops);
+CREATE INDEX beatmaps_mode_difficulty_rating_idx ON beatmaps (mode, difficulty_rating);
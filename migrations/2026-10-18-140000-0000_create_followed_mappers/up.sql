@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS followed_mappers (
+    guild_id BIGINT NOT NULL,
+    mapper_id BIGINT NOT NULL,
+    username TEXT NOT NULL,
+    last_event TIMESTAMPTZ DEFAULT now() NOT NULL,
+    PRIMARY KEY (guild_id, mapper_id)
+);
+
+CREATE INDEX followed_mappers_mapper_id_idx ON followed_mappers (mapper_id);
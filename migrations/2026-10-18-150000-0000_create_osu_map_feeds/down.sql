@@ -0,0 +1,3 @@
+DROP TABLE osu_map_feed_cursors;
+
+DROP TABLE osu_map_feeds;
@@ -0,0 +1,17 @@
+CREATE TABLE IF NOT EXISTS osu_map_feeds (
+    guild_id BIGINT NOT NULL PRIMARY KEY,
+    qualified BOOLEAN NOT NULL DEFAULT TRUE,
+    ranked BOOLEAN NOT NULL DEFAULT TRUE,
+    loved BOOLEAN NOT NULL DEFAULT TRUE,
+    mode VARCHAR(7),
+    min_stars FLOAT8,
+    max_stars FLOAT8,
+    max_length INTEGER,
+    tags TEXT
+);
+
+CREATE TABLE IF NOT EXISTS osu_map_feed_cursors (
+    status VARCHAR(9) NOT NULL PRIMARY KEY,
+    last_date TIMESTAMPTZ NOT NULL,
+    last_ids BIGINT[] NOT NULL
+);
@@ -0,0 +1 @@
+DROP TABLE osu_score_rules;
@@ -0,0 +1,10 @@
+CREATE TABLE IF NOT EXISTS osu_score_rules (
+    guild_id BIGINT NOT NULL,
+    channel_id BIGINT NOT NULL,
+    top_n INTEGER,
+    min_pp FLOAT8,
+    modes VARCHAR(7)[],
+    include_unranked BOOLEAN NOT NULL DEFAULT TRUE,
+    show_diff BOOLEAN NOT NULL DEFAULT TRUE,
+    PRIMARY KEY (guild_id, channel_id)
+);
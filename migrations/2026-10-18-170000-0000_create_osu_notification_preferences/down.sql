@@ -0,0 +1 @@
+DROP TABLE osu_notification_preferences;
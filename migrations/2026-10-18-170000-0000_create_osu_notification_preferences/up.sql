@@ -0,0 +1,6 @@
+CREATE TABLE IF NOT EXISTS osu_notification_preferences (
+    id BIGINT NOT NULL PRIMARY KEY,
+    muted_guilds BIGINT[] NOT NULL DEFAULT '{}',
+    paused_until TIMESTAMPTZ,
+    direct_message BOOLEAN NOT NULL DEFAULT FALSE
+);
@@ -0,0 +1,9 @@
+DELETE FROM osu_users
+WHERE NOT EXISTS (
+    SELECT 1 FROM linked_osu_profiles
+    WHERE linked_osu_profiles.osu_id = osu_users.id AND linked_osu_profiles.mode = osu_users.mode
+);
+ALTER TABLE osu_users DROP CONSTRAINT osu_users_pkey;
+ALTER TABLE osu_users ADD PRIMARY KEY (id);
+
+ALTER TABLE linked_osu_profiles DROP COLUMN modes;
@@ -0,0 +1,5 @@
+ALTER TABLE linked_osu_profiles ADD COLUMN modes VARCHAR(7)[] NOT NULL DEFAULT '{}';
+UPDATE linked_osu_profiles SET modes = ARRAY[mode];
+
+ALTER TABLE osu_users DROP CONSTRAINT osu_users_pkey;
+ALTER TABLE osu_users ADD PRIMARY KEY (id, mode);
@@ -0,0 +1,6 @@
+DELETE FROM linked_osu_profiles WHERE NOT is_primary;
+
+ALTER TABLE linked_osu_profiles DROP CONSTRAINT linked_osu_profiles_pkey;
+ALTER TABLE linked_osu_profiles ADD PRIMARY KEY (id);
+
+ALTER TABLE linked_osu_profiles DROP COLUMN is_primary;
@@ -0,0 +1,4 @@
+ALTER TABLE linked_osu_profiles ADD COLUMN is_primary BOOLEAN NOT NULL DEFAULT TRUE;
+
+ALTER TABLE linked_osu_profiles DROP CONSTRAINT linked_osu_profiles_pkey;
+ALTER TABLE linked_osu_profiles ADD PRIMARY KEY (id, osu_id);
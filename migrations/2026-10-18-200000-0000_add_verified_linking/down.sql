@@ -0,0 +1,2 @@
+ALTER TABLE osu_guild_channels DROP COLUMN require_verified;
+ALTER TABLE linked_osu_profiles DROP COLUMN verified;
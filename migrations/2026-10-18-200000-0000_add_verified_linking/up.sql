@@ -0,0 +1,2 @@
+ALTER TABLE linked_osu_profiles ADD COLUMN verified BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE osu_guild_channels ADD COLUMN require_verified BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1 @@
+ALTER TABLE beatmaps DROP COLUMN hp;
@@ -0,0 +1 @@
+ALTER TABLE beatmaps ADD COLUMN hp FLOAT8;
@@ -0,0 +1 @@
+ALTER TABLE osu_guild_channels DROP COLUMN timestamp_channel;
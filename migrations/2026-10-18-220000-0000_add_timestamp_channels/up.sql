@@ -0,0 +1 @@
+ALTER TABLE osu_guild_channels ADD COLUMN timestamp_channel BIGINT[];
@@ -0,0 +1,12 @@
+DROP INDEX osu_files_time_cached_idx;
+
+CREATE TRIGGER mdt_osu_files
+    BEFORE UPDATE ON osu_files
+    FOR EACH ROW
+    EXECUTE PROCEDURE moddatetime (time_cached);
+
+-- This is lossy: Postgres can't decompress zstd, so compressed files are dropped instead of
+-- being turned back into plain text. They get downloaded again when they're needed.
+DELETE FROM osu_files WHERE compressed;
+
+ALTER TABLE osu_files DROP COLUMN compressed;
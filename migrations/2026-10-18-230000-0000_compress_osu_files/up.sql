@@ -0,0 +1,9 @@
+-- Existing rows are compressed in place by osu_file::compress_existing when the bot starts,
+-- since Postgres can't zstd them on its own.
+ALTER TABLE osu_files ADD COLUMN compressed BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- time_cached is now refreshed whenever a file gets used, so the cache can evict the least
+-- recently used files first. Compressing a row shouldn't count as using it.
+DROP TRIGGER mdt_osu_files ON osu_files;
+
+CREATE INDEX osu_files_time_cached_idx ON osu_files (time_cached);
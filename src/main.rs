@@ -3,8 +3,12 @@ mod plugins;
 pub mod schema;
 mod utils;
 
+use crate::utils::db::osu_file;
+use crate::utils::osu::map_feed::MapFeed;
+use crate::utils::osu::playfield;
 use crate::utils::osu::scores_ws::ScoresWs;
 use crate::utils::osu::tracking::OsuTracker;
+use crate::utils::osu::verification::{self, VerificationServer};
 use chrono::{DateTime, Utc};
 use diesel_async::pooled_connection::AsyncDieselConnectionManager;
 use diesel_async::{AsyncMigrationHarness, AsyncPgConnection};
@@ -84,6 +88,19 @@ impl EventHandler for Handler {
                         }
                     });
 
+                    let mut map_feed = MapFeed {
+                        http: ctx.http.clone(),
+                        osu_client: ctx.data::<Data>().osu_client.clone(),
+                        pool: ctx.data::<Data>().db_pool.clone(),
+                    };
+
+                    tokio::spawn(async move {
+                        match map_feed.feed_loop().await {
+                            Ok(()) => {}
+                            Err(why) => error!("{why}"),
+                        }
+                    });
+
                     let cloned_ctx = ctx.clone();
                     tokio::spawn(async move {
                         tokio::signal::ctrl_c()
@@ -104,6 +121,10 @@ impl EventHandler for Handler {
                     Ok(()) => {}
                     Err(e) => error!("{e}"),
                 }
+                match playfield::respond_to_timestamp(ctx, new_message, &ctx.data::<Data>()).await {
+                    Ok(()) => {}
+                    Err(e) => error!("{e}"),
+                }
             }
             FullEvent::VoiceStateUpdate { old, .. } => {
                 let Some(voice) = old else { return };
@@ -176,6 +197,33 @@ async fn main() {
     }
     drop(harness);
 
+    // Files cached before compression was added get compressed in the background. Failed passes
+    // are retried until every file is compressed.
+    let compression_pool = db_pool.clone();
+    tokio::spawn(async move {
+        loop {
+            let connection = &mut match compression_pool.get().await {
+                Ok(connection) => connection,
+                Err(why) => {
+                    error!("Failed to connect to database {}", why);
+                    tokio::time::sleep(tokio::time::Duration::from_secs(60)).await;
+                    continue;
+                }
+            };
+            match osu_file::compress_existing(connection).await {
+                Ok(0) => break,
+                Ok(count) => {
+                    info!("Finished compressing {count} cached osu! files");
+                    break;
+                }
+                Err(why) => {
+                    error!("Failed to compress cached osu! files, retrying: {why}");
+                    tokio::time::sleep(tokio::time::Duration::from_secs(60)).await;
+                }
+            }
+        }
+    });
+
     let builder = PrometheusBuilder::new()
         .with_http_listener(SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), 9326));
 
@@ -187,6 +235,20 @@ async fn main() {
         .install()
         .expect("failed to install recorder/exporter");
 
+    if verification::is_enabled() {
+        let verification_server = VerificationServer {
+            http_client: reqwest::Client::new(),
+            pool: db_pool.clone(),
+        };
+
+        tokio::spawn(async move {
+            match verification_server.listen().await {
+                Ok(()) => {}
+                Err(why) => error!("{why}"),
+            }
+        });
+    }
+
     let options = poise::FrameworkOptions {
         commands: vec![
             plugins::basic::help(),
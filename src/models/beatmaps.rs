@@ -32,6 +32,8 @@ pub struct Beatmap {
     pub user_id: i64,
     pub version: String,
     pub time_cached: chrono::DateTime<chrono::Utc>,
+    pub od: Option<f64>,
+    pub hp: Option<f64>,
 }
 
 #[derive(
@@ -69,4 +71,6 @@ pub struct NewBeatmap {
     pub total_length: i32,
     pub user_id: i64,
     pub version: String,
+    pub od: Option<f64>,
+    pub hp: Option<f64>,
 }
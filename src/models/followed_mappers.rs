@@ -0,0 +1,20 @@
+use crate::schema::followed_mappers;
+use diesel::{Identifiable, Insertable, Queryable};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Queryable, Identifiable)]
+#[diesel(table_name=followed_mappers, primary_key(guild_id, mapper_id))]
+pub struct FollowedMapper {
+    pub guild_id: i64,
+    pub mapper_id: i64,
+    pub username: String,
+    pub last_event: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Insertable)]
+#[diesel(table_name=followed_mappers)]
+pub struct NewFollowedMapper {
+    pub guild_id: i64,
+    pub mapper_id: i64,
+    pub username: String,
+}
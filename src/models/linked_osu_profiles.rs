@@ -3,13 +3,16 @@ use diesel::{AsChangeset, Identifiable, Insertable, Queryable};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, Clone, Queryable, Identifiable)]
-#[diesel(table_name=linked_osu_profiles, primary_key(id))]
+#[diesel(table_name=linked_osu_profiles, primary_key(id, osu_id))]
 pub struct LinkedOsuProfile {
     pub id: i64,
     pub osu_id: i64,
     pub home_guild: i64,
     pub mode: String,
     pub minimal_formatting: bool,
+    pub modes: Vec<Option<String>>,
+    pub is_primary: bool,
+    pub verified: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Insertable, AsChangeset)]
@@ -20,4 +23,7 @@ pub struct NewLinkedOsuProfile {
     pub home_guild: i64,
     pub mode: String,
     pub minimal_formatting: bool,
+    pub modes: Vec<Option<String>>,
+    pub is_primary: bool,
+    pub verified: bool,
 }
@@ -12,6 +12,7 @@ pub struct OsuFile {
     pub id: i64,
     pub file: Vec<u8>,
     pub time_cached: chrono::DateTime<chrono::Utc>,
+    pub compressed: bool,
 }
 
 #[derive(
@@ -31,4 +32,5 @@ pub struct OsuFile {
 pub struct NewOsuFile {
     pub id: i64,
     pub file: Vec<u8>,
+    pub compressed: bool,
 }
@@ -8,6 +8,8 @@ pub struct OsuGuildChannel {
     pub guild_id: i64,
     pub score_channel: Option<Vec<Option<i64>>>,
     pub map_channel: Option<Vec<Option<i64>>>,
+    pub require_verified: bool,
+    pub timestamp_channel: Option<Vec<Option<i64>>>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Insertable, AsChangeset)]
@@ -16,4 +18,6 @@ pub struct NewOsuGuildChannel {
     pub guild_id: i64,
     pub score_channel: Option<Vec<Option<i64>>>,
     pub map_channel: Option<Vec<Option<i64>>>,
+    pub require_verified: bool,
+    pub timestamp_channel: Option<Vec<Option<i64>>>,
 }
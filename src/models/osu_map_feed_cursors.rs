@@ -0,0 +1,19 @@
+use crate::schema::osu_map_feed_cursors;
+use diesel::{AsChangeset, Identifiable, Insertable, Queryable};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Queryable, Identifiable)]
+#[diesel(table_name=osu_map_feed_cursors, primary_key(status))]
+pub struct OsuMapFeedCursor {
+    pub status: String,
+    pub last_date: chrono::DateTime<chrono::Utc>,
+    pub last_ids: Vec<Option<i64>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Insertable, AsChangeset)]
+#[diesel(table_name=osu_map_feed_cursors)]
+pub struct NewOsuMapFeedCursor {
+    pub status: String,
+    pub last_date: chrono::DateTime<chrono::Utc>,
+    pub last_ids: Vec<Option<i64>>,
+}
@@ -0,0 +1,31 @@
+use crate::schema::osu_map_feeds;
+use diesel::{AsChangeset, Identifiable, Insertable, Queryable};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Queryable, Identifiable)]
+#[diesel(table_name=osu_map_feeds, primary_key(guild_id))]
+pub struct OsuMapFeed {
+    pub guild_id: i64,
+    pub qualified: bool,
+    pub ranked: bool,
+    pub loved: bool,
+    pub mode: Option<String>,
+    pub min_stars: Option<f64>,
+    pub max_stars: Option<f64>,
+    pub max_length: Option<i32>,
+    pub tags: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Insertable, AsChangeset)]
+#[diesel(table_name=osu_map_feeds, treat_none_as_null = true)]
+pub struct NewOsuMapFeed {
+    pub guild_id: i64,
+    pub qualified: bool,
+    pub ranked: bool,
+    pub loved: bool,
+    pub mode: Option<String>,
+    pub min_stars: Option<f64>,
+    pub max_stars: Option<f64>,
+    pub max_length: Option<i32>,
+    pub tags: Option<String>,
+}
@@ -0,0 +1,21 @@
+use crate::schema::osu_notification_preferences;
+use diesel::{AsChangeset, Identifiable, Insertable, Queryable};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Queryable, Identifiable)]
+#[diesel(table_name=osu_notification_preferences, primary_key(id))]
+pub struct OsuNotificationPreference {
+    pub id: i64,
+    pub muted_guilds: Vec<Option<i64>>,
+    pub paused_until: Option<chrono::DateTime<chrono::Utc>>,
+    pub direct_message: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Insertable, AsChangeset)]
+#[diesel(table_name=osu_notification_preferences, treat_none_as_null = true)]
+pub struct NewOsuNotificationPreference {
+    pub id: i64,
+    pub muted_guilds: Vec<Option<i64>>,
+    pub paused_until: Option<chrono::DateTime<chrono::Utc>>,
+    pub direct_message: bool,
+}
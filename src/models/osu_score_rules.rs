@@ -0,0 +1,27 @@
+use crate::schema::osu_score_rules;
+use diesel::{AsChangeset, Identifiable, Insertable, Queryable};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Queryable, Identifiable)]
+#[diesel(table_name=osu_score_rules, primary_key(guild_id, channel_id))]
+pub struct OsuScoreRule {
+    pub guild_id: i64,
+    pub channel_id: i64,
+    pub top_n: Option<i32>,
+    pub min_pp: Option<f64>,
+    pub modes: Option<Vec<Option<String>>>,
+    pub include_unranked: bool,
+    pub show_diff: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Insertable, AsChangeset)]
+#[diesel(table_name=osu_score_rules, treat_none_as_null = true)]
+pub struct NewOsuScoreRule {
+    pub guild_id: i64,
+    pub channel_id: i64,
+    pub top_n: Option<i32>,
+    pub min_pp: Option<f64>,
+    pub modes: Option<Vec<Option<String>>>,
+    pub include_unranked: bool,
+    pub show_diff: bool,
+}
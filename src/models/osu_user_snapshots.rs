@@ -0,0 +1,31 @@
+use crate::schema::osu_user_snapshots;
+use diesel::{Identifiable, Insertable, Queryable};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Queryable, Identifiable)]
+#[diesel(table_name=osu_user_snapshots, primary_key(id))]
+pub struct OsuUserSnapshot {
+    pub id: i64,
+    pub osu_id: i64,
+    pub mode: String,
+    pub pp: f64,
+    pub accuracy: f64,
+    pub global_rank: i32,
+    pub country_rank: i32,
+    pub playcount: i32,
+    pub ranked_score: i64,
+    pub time_taken: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Insertable)]
+#[diesel(table_name=osu_user_snapshots)]
+pub struct NewOsuUserSnapshot {
+    pub osu_id: i64,
+    pub mode: String,
+    pub pp: f64,
+    pub accuracy: f64,
+    pub global_rank: i32,
+    pub country_rank: i32,
+    pub playcount: i32,
+    pub ranked_score: i64,
+}
@@ -3,7 +3,7 @@ use diesel::{AsChangeset, Identifiable, Insertable, Queryable};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, Clone, Queryable, Identifiable)]
-#[diesel(table_name=osu_users, primary_key(id))]
+#[diesel(table_name=osu_users, primary_key(id, mode))]
 pub struct OsuUser {
     pub id: i64,
     pub username: String,
@@ -19,6 +19,7 @@ pub struct OsuUser {
     pub ticks: i32,
     pub time_cached: chrono::DateTime<chrono::Utc>,
     pub min_pp: f64,
+    pub playcount: i32,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Insertable, AsChangeset)]
@@ -38,6 +39,7 @@ pub struct NewOsuUser {
     pub ranked_score: i64,
     pub time_cached: chrono::DateTime<chrono::Utc>,
     pub min_pp: f64,
+    pub playcount: i32,
 }
 
 impl NewOsuUser {
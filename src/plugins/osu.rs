@@ -1,31 +1,67 @@
-use crate::models::linked_osu_profiles::NewLinkedOsuProfile;
+use crate::models::followed_mappers::NewFollowedMapper;
+use crate::models::linked_osu_profiles::{LinkedOsuProfile, NewLinkedOsuProfile};
 use crate::models::osu_guild_channels::NewOsuGuildChannel;
+use crate::models::osu_map_feeds::NewOsuMapFeed;
 use crate::models::osu_notifications::NewOsuNotification;
+use crate::models::osu_score_rules::NewOsuScoreRule;
+use crate::models::osu_users::NewOsuUser;
+use crate::utils::db::beatmaps::BeatmapSearch;
 use crate::utils::db::{
-    beatmaps, beatmapsets, linked_osu_profiles, osu_file, osu_guild_channels, osu_notifications,
-    osu_users,
+    beatmaps, beatmapsets, followed_mappers, linked_osu_profiles, osu_file, osu_guild_channels,
+    osu_map_feeds, osu_notification_preferences, osu_notifications, osu_score_rules,
+    osu_user_snapshots, osu_users,
+};
+use crate::utils::misc::{get_reply, remove_trailing_zeros};
+use crate::utils::osu::caching::{get_beatmap, get_beatmap_by_checksum, get_beatmapset};
+use crate::utils::osu::calculate::{
+    SimulatedScore, calculate, calculate_replay, calculate_simulated,
 };
-use crate::utils::misc::get_reply;
-use crate::utils::osu::caching::{get_beatmap, get_beatmapset};
-use crate::utils::osu::calculate::calculate;
 use crate::utils::osu::card::render_card;
-use crate::utils::osu::embeds::{send_score_embed, send_scores_embed};
-use crate::utils::osu::map_format::format_map_status;
+use crate::utils::osu::embeds::{send_list_embed, send_score_embed, send_scores_embed};
+use crate::utils::osu::graphs::history::{HistorySeries, render_history};
+use crate::utils::osu::graphs::replay::render_replay_analysis;
+use crate::utils::osu::graphs::strains::{get_map_strains, render_strains};
+use crate::utils::osu::leaderboard::{
+    LeaderboardEntry, LeaderboardSortChoices, ServerScore, format_leaderboard,
+    format_server_scores, get_guild_profiles, sort_leaderboard,
+};
+use crate::utils::osu::map_format::{format_map_status, format_search_result};
+use crate::utils::osu::matches::{
+    MatchTracker, TRACKED_MATCHES, calculate_match_costs, finished_games, format_match_costs,
+    get_full_match,
+};
 use crate::utils::osu::misc::{
-    add_profile_data, calculate_potential_acc, find_beatmap_link, get_osu_user, get_user,
-    is_playing, set_up_score_list, sort_scores, wipe_profile_data,
+    add_profile_data, calculate_potential_acc, calculate_pp_needed, find_beatmap_link,
+    gamemode_from_string, get_command_beatmap, get_osu_user, get_user, is_playing, parse_alt_flag,
+    set_up_score_list, sort_scores, wipe_profile_data,
+};
+use crate::utils::osu::misc_format::{
+    fmt_with_settings, format_beatmap_link, format_missing_user_string, format_rank_status,
+    format_user_link,
 };
-use crate::utils::osu::misc_format::format_missing_user_string;
-use crate::utils::osu::regex::{BeatmapInfo, get_beatmap_info};
-use crate::utils::osu::scores_ws;
+use crate::utils::osu::playfield::create_snapshot;
+use crate::utils::osu::recommend::{
+    RecommendChoices, build_skill_profile, format_skill_profile, get_recommendations,
+};
+use crate::utils::osu::regex::{BeatmapInfo, get_beatmap_info, get_editor_timestamp, get_match_id};
+use crate::utils::osu::replay::{parse_replay, unstable_rate};
+use crate::utils::osu::score_format::{format_replay, format_simulated_score};
+use crate::utils::osu::{scores_ws, verification};
 use crate::{Context, Error};
 use chrono::Utc;
-use poise::CreateReply;
+use dashmap::DashMap;
+use dashmap::mapref::entry::Entry;
+use diesel_async::AsyncPgConnection;
+use foldhash::HashSet;
 use poise::serenity_prelude::model::colour::colours::roles::BLUE;
 use poise::serenity_prelude::{
-    CreateAttachment, CreateEmbed, CreateEmbedAuthor, GetMessages, GuildChannel, UserId,
+    Attachment, CreateAttachment, CreateEmbed, CreateEmbedAuthor, CreateEmbedFooter, GetMessages,
+    GuildChannel, UserId,
 };
+use poise::{ChoiceParameter, CreateReply};
 use rosu_v2::model::GameMode;
+use rosu_v2::prelude::{GameModsIntermode, MatchGame, RankStatus};
+use tracing::error;
 
 /// Display information about your osu! user.
 #[poise::command(
@@ -38,6 +74,9 @@ use rosu_v2::model::GameMode;
         "score",
         "scores",
         "unlink",
+        "accounts",
+        "primary",
+        "verify",
         "mode",
         "recent",
         "recent_best",
@@ -47,9 +86,28 @@ use rosu_v2::model::GameMode;
         "top",
         "score_notifications",
         "map_notifications",
+        "timestamp_channels",
         "delete_guild_config",
         "debug",
-        "minimal_formatting"
+        "minimal_formatting",
+        "replay",
+        "simulate",
+        "ppneeded",
+        "history",
+        "leaderboard",
+        "serverscores",
+        "osu_match",
+        "search",
+        "graph",
+        "playfield",
+        "recommend",
+        "follow",
+        "map_feed",
+        "delete_map_feed",
+        "score_rules",
+        "delete_score_rules",
+        "notifications",
+        "require_verification"
     )
 )]
 pub async fn osu(
@@ -81,7 +139,14 @@ pub async fn osu(
 
     let author = CreateEmbedAuthor::new(discord_user.name.clone()).icon_url(discord_user.face());
 
-    let card = render_card(&osu_user, color).await?.encode_png()?;
+    let with_rank_graph = osu_user
+        .rank_history
+        .as_ref()
+        .is_some_and(|rank_history| rank_history.iter().filter(|rank| **rank > 0).count() > 1);
+
+    let card = render_card(&osu_user, color, with_rank_graph)
+        .await?
+        .encode_png()?;
 
     let embed = CreateEmbed::new()
         .image(
@@ -99,7 +164,7 @@ pub async fn osu(
     Ok(())
 }
 
-/// Link an osu! profile.
+/// Link an osu! profile. Add `--alt` to link it as an alt account.
 #[poise::command(
     prefix_command,
     slash_command,
@@ -114,19 +179,42 @@ pub async fn link(
     username: String,
 ) -> Result<(), Error> {
     ctx.defer().await?;
+    let (username, alt) = parse_alt_flag(&username);
     let user = ctx.data().osu_client.user(username).await?;
     let connection = &mut ctx.data().db_pool.get().await?;
 
-    if let Ok(profile) =
-        linked_osu_profiles::read(connection, i64::try_from(ctx.author().id.get())?).await
+    let discord_id = i64::try_from(ctx.author().id.get())?;
+    let accounts = linked_osu_profiles::get_accounts(connection, discord_id).await?;
+
+    if accounts
+        .iter()
+        .any(|account| account.osu_id == i64::from(user.user_id))
     {
-        linked_osu_profiles::delete(connection, profile.id).await?;
-        scores_ws::remove_tracked_user(profile.id, profile.osu_id);
-        wipe_profile_data(connection, profile.osu_id).await?;
+        if alt.is_some() {
+            ctx.say(format!(
+                "`{}` is already linked to your account.",
+                user.username.as_str()
+            ))
+            .await?;
+        } else {
+            linked_osu_profiles::set_primary(connection, discord_id, i64::from(user.user_id))
+                .await?;
+            ctx.say(format!(
+                "Set your primary osu! profile to `{}`.",
+                user.username.as_str()
+            ))
+            .await?;
+        }
+        return Ok(());
     }
 
+    // Linking without `--alt` makes the new account the primary one and keeps the old one as an
+    // alt, and the first account linked is always the primary one.
+    let primary = accounts.iter().find(|account| account.is_primary);
+    let is_primary = alt.is_none() || primary.is_none();
+
     let query_item = NewLinkedOsuProfile {
-        id: i64::try_from(ctx.author().id.get())?,
+        id: discord_id,
         osu_id: i64::from(user.user_id),
         home_guild: i64::try_from(
             ctx.guild_id()
@@ -134,7 +222,10 @@ pub async fn link(
                 .get(),
         )?,
         mode: user.mode.to_string(),
-        minimal_formatting: false,
+        minimal_formatting: primary.is_some_and(|profile| profile.minimal_formatting),
+        modes: vec![Some(user.mode.to_string())],
+        is_primary,
+        verified: false,
     };
 
     let notification_item = NewOsuNotification {
@@ -146,21 +237,36 @@ pub async fn link(
 
     linked_osu_profiles::create(connection, &query_item).await?;
 
-    scores_ws::add_tracked_user(
-        i64::try_from(ctx.author().id.get())?,
-        i64::from(user.user_id),
-    );
+    if is_primary && primary.is_some() {
+        linked_osu_profiles::set_primary(connection, discord_id, i64::from(user.user_id)).await?;
+    }
 
-    ctx.say(format!(
-        "Set your osu! profile to `{}`.",
-        user.username.as_str()
-    ))
-    .await?;
+    scores_ws::add_tracked_user(discord_id, i64::from(user.user_id));
+
+    if is_primary && primary.is_some() {
+        ctx.say(format!(
+            "Set your primary osu! profile to `{}`, your previous one is kept as an alt account.",
+            user.username.as_str()
+        ))
+        .await?;
+    } else if is_primary {
+        ctx.say(format!(
+            "Set your osu! profile to `{}`.",
+            user.username.as_str()
+        ))
+        .await?;
+    } else {
+        ctx.say(format!(
+            "Linked `{}` as an alt account.",
+            user.username.as_str()
+        ))
+        .await?;
+    }
 
     Ok(())
 }
 
-/// Unlink your osu! profile.
+/// Unlink your osu! profile. Leave out the username to unlink every account.
 #[poise::command(
     prefix_command,
     slash_command,
@@ -168,25 +274,179 @@ pub async fn link(
     category = "osu!",
     aliases("unset")
 )]
-pub async fn unlink(ctx: Context<'_>) -> Result<(), Error> {
+pub async fn unlink(
+    ctx: Context<'_>,
+    #[rest]
+    #[description = "Linked osu! username to unlink"]
+    username: Option<String>,
+) -> Result<(), Error> {
     ctx.defer().await?;
     let connection = &mut ctx.data().db_pool.get().await?;
-    let profile =
-        linked_osu_profiles::read(connection, i64::try_from(ctx.author().id.get())?).await;
+    let accounts =
+        linked_osu_profiles::get_accounts(connection, i64::try_from(ctx.author().id.get())?)
+            .await?;
 
-    match profile {
-        Ok(profile) => {
-            linked_osu_profiles::delete(connection, profile.id).await?;
+    if accounts.is_empty() {
+        ctx.say(format_missing_user_string(ctx, ctx.author()).await?)
+            .await?;
+        return Ok(());
+    }
+
+    let Some(username) = username else {
+        linked_osu_profiles::delete(connection, i64::try_from(ctx.author().id.get())?).await?;
+        for profile in accounts {
             wipe_profile_data(connection, profile.osu_id).await?;
             scores_ws::remove_tracked_user(profile.id, profile.osu_id);
-            ctx.say("Unlinked your profile.").await?;
-        }
-        Err(_) => {
-            ctx.say(format_missing_user_string(ctx, ctx.author()).await?)
-                .await?;
         }
+        ctx.say("Unlinked your profile.").await?;
+        return Ok(());
+    };
+
+    let Some(profile) = find_linked_account(ctx, &accounts, &username).await? else {
+        ctx.say(format!("`{username}` isn't linked to your account."))
+            .await?;
+        return Ok(());
+    };
+
+    linked_osu_profiles::delete_account(connection, profile.id, profile.osu_id).await?;
+    wipe_profile_data(connection, profile.osu_id).await?;
+    scores_ws::remove_tracked_user(profile.id, profile.osu_id);
+
+    if profile.is_primary
+        && let Some(next) = accounts
+            .iter()
+            .find(|account| account.osu_id != profile.osu_id)
+    {
+        linked_osu_profiles::set_primary(connection, next.id, next.osu_id).await?;
+    }
+
+    ctx.say(format!("Unlinked `{username}` from your account."))
+        .await?;
+
+    Ok(())
+}
+
+async fn find_linked_account<'a>(
+    ctx: Context<'_>,
+    accounts: &'a [LinkedOsuProfile],
+    username: &str,
+) -> Result<Option<&'a LinkedOsuProfile>, Error> {
+    let Ok(user) = ctx.data().osu_client.user(username).await else {
+        return Ok(None);
+    };
+
+    Ok(accounts
+        .iter()
+        .find(|account| account.osu_id == i64::from(user.user_id)))
+}
+
+/// List the osu! accounts linked to your Discord account.
+#[poise::command(prefix_command, slash_command, category = "osu!", aliases("alts"))]
+pub async fn accounts(ctx: Context<'_>) -> Result<(), Error> {
+    ctx.defer().await?;
+    let connection = &mut ctx.data().db_pool.get().await?;
+    let accounts =
+        linked_osu_profiles::get_accounts(connection, i64::try_from(ctx.author().id.get())?)
+            .await?;
+
+    if accounts.is_empty() {
+        ctx.say(format_missing_user_string(ctx, ctx.author()).await?)
+            .await?;
+        return Ok(());
+    }
+
+    let mut formatted_accounts = Vec::new();
+    let mut alt_position = 0;
+    for account in &accounts {
+        let username = match osu_users::read(connection, account.osu_id, &account.mode).await {
+            Ok(osu_user) => osu_user.username,
+            Err(_) => account.osu_id.to_string(),
+        };
+        let label = if account.is_primary {
+            String::from("Primary")
+        } else {
+            alt_position += 1;
+            format!("`--alt {alt_position}`")
+        };
+        formatted_accounts.push(format!(
+            "{label}: [{username}]({}){}",
+            format_user_link(account.osu_id),
+            if account.verified { " (verified)" } else { "" }
+        ));
+    }
+
+    let embed = CreateEmbed::new()
+        .title("Linked osu! accounts")
+        .description(formatted_accounts.join("\n"))
+        .color(BLUE);
+
+    ctx.send(CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+/// Change which of your linked osu! accounts commands use by default.
+#[poise::command(prefix_command, slash_command, category = "osu!")]
+pub async fn primary(
+    ctx: Context<'_>,
+    #[rest]
+    #[description = "Linked osu! username to make primary"]
+    username: String,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+    let connection = &mut ctx.data().db_pool.get().await?;
+    let accounts =
+        linked_osu_profiles::get_accounts(connection, i64::try_from(ctx.author().id.get())?)
+            .await?;
+
+    let Some(profile) = find_linked_account(ctx, &accounts, &username).await? else {
+        ctx.say(format!("`{username}` isn't linked to your account."))
+            .await?;
+        return Ok(());
+    };
+
+    linked_osu_profiles::set_primary(connection, profile.id, profile.osu_id).await?;
+
+    ctx.say(format!("Set your primary osu! profile to `{username}`."))
+        .await?;
+
+    Ok(())
+}
+
+/// Verify that you own your linked osu! accounts by logging in with osu!.
+#[poise::command(slash_command, category = "osu!")]
+pub async fn verify(ctx: Context<'_>) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    if !verification::is_enabled() {
+        ctx.say("Account verification isn't set up for this bot.")
+            .await?;
+        return Ok(());
+    }
+
+    let connection = &mut ctx.data().db_pool.get().await?;
+    let discord_id = i64::try_from(ctx.author().id.get())?;
+    if linked_osu_profiles::get_accounts(connection, discord_id)
+        .await?
+        .is_empty()
+    {
+        ctx.say(format_missing_user_string(ctx, ctx.author()).await?)
+            .await?;
+        return Ok(());
     }
 
+    let authorize_url = verification::create_authorize_url(discord_id, &ctx.author().name)?;
+
+    ctx.send(
+        CreateReply::default()
+            .content(format!(
+                "Log in with the osu! account you want to verify [here](<{authorize_url}>). \
+                 The link can only be used once, so don't share it."
+            ))
+            .ephemeral(true),
+    )
+    .await?;
+
     Ok(())
 }
 
@@ -224,6 +484,32 @@ impl From<GameModeChoices> for GameMode {
     }
 }
 
+#[derive(poise::ChoiceParameter, Clone, Copy)]
+pub enum MapStatusChoices {
+    Ranked,
+    Approved,
+    Qualified,
+    Loved,
+    Pending,
+    #[name = "WIP"]
+    Wip,
+    Graveyard,
+}
+
+impl From<MapStatusChoices> for RankStatus {
+    fn from(status: MapStatusChoices) -> RankStatus {
+        match status {
+            MapStatusChoices::Ranked => RankStatus::Ranked,
+            MapStatusChoices::Approved => RankStatus::Approved,
+            MapStatusChoices::Qualified => RankStatus::Qualified,
+            MapStatusChoices::Loved => RankStatus::Loved,
+            MapStatusChoices::Pending => RankStatus::Pending,
+            MapStatusChoices::Wip => RankStatus::WIP,
+            MapStatusChoices::Graveyard => RankStatus::Graveyard,
+        }
+    }
+}
+
 #[poise::command(prefix_command, slash_command, category = "osu!")]
 pub async fn minimal_formatting(
     ctx: Context<'_>,
@@ -241,6 +527,9 @@ pub async fn minimal_formatting(
                 home_guild: profile.home_guild,
                 mode: profile.mode,
                 minimal_formatting: minimal,
+                modes: profile.modes,
+                is_primary: profile.is_primary,
+                verified: profile.verified,
             };
 
             linked_osu_profiles::update(connection, profile.id, &query_item).await?;
@@ -257,152 +546,323 @@ pub async fn minimal_formatting(
     Ok(())
 }
 
-/// Change your osu! mode.
+/// Manage the osu! modes you're tracked in.
 #[poise::command(
     prefix_command,
     slash_command,
     category = "osu!",
-    aliases("mode", "m", "track")
+    aliases("modes", "m", "track"),
+    subcommands("mode_add", "mode_remove", "mode_default", "mode_list")
 )]
 pub async fn mode(
     ctx: Context<'_>,
-    #[description = "Gamemode to switch to."] new_mode: GameModeChoices,
+    #[description = "Gamemode to switch to."] new_mode: Option<GameModeChoices>,
+    #[description = "Alt account to change, 1 being your first alt."] alt: Option<usize>,
 ) -> Result<(), Error> {
-    ctx.defer().await?;
-    let connection = &mut ctx.data().db_pool.get().await?;
-    let profile =
-        linked_osu_profiles::read(connection, i64::try_from(ctx.author().id.get())?).await;
-
-    let mode: GameMode = new_mode.into();
-
-    match profile {
-        Ok(profile) => {
-            let query_item = NewLinkedOsuProfile {
-                id: profile.id,
-                osu_id: profile.osu_id,
-                home_guild: profile.home_guild,
-                mode: mode.to_string(),
-                minimal_formatting: profile.minimal_formatting,
-            };
+    // `osu mode <mode>` keeps working as a shortcut for changing the default mode.
+    let Some(new_mode) = new_mode else {
+        ctx.say("Usage: `osu mode <add|remove|default|list>`, or `osu mode <mode>` to change your default mode.")
+            .await?;
+        return Ok(());
+    };
 
-            linked_osu_profiles::update(connection, profile.id, &query_item).await?;
-            wipe_profile_data(connection, profile.osu_id).await?;
+    set_default_mode(ctx, new_mode, alt).await
+}
 
-            add_profile_data(
-                ctx.data().osu_client.clone(),
-                u32::try_from(profile.osu_id)?,
-                mode,
-                connection,
-            )
-            .await?;
+/// Reads the account the mode commands change, the primary one unless `alt` picks an alt.
+async fn read_mode_account(
+    ctx: Context<'_>,
+    connection: &mut AsyncPgConnection,
+    alt: Option<usize>,
+) -> Result<Option<LinkedOsuProfile>, Error> {
+    let discord_id = i64::try_from(ctx.author().id.get())?;
 
-            ctx.say(format!("Updated your osu! mode to {mode}."))
-                .await?;
-        }
-        Err(_) => {
+    let Some(position) = alt else {
+        let Ok(profile) = linked_osu_profiles::read(connection, discord_id).await else {
             ctx.say(format_missing_user_string(ctx, ctx.author()).await?)
                 .await?;
-        }
+            return Ok(None);
+        };
+        return Ok(Some(profile));
+    };
+
+    let alt_profile = linked_osu_profiles::get_accounts(connection, discord_id)
+        .await?
+        .into_iter()
+        .filter(|profile| !profile.is_primary)
+        .nth(position.saturating_sub(1));
+
+    if alt_profile.is_none() {
+        ctx.say(format!("You don't have an alt account #{position} linked."))
+            .await?;
     }
 
-    Ok(())
+    Ok(alt_profile)
 }
 
-/// Display your score on a beatmap.
-#[poise::command(prefix_command, slash_command, category = "osu!", aliases("c"))]
-pub async fn mapinfo(
+/// Start tracking your scores in another osu! mode.
+#[poise::command(prefix_command, slash_command, category = "osu!", rename = "add")]
+pub async fn mode_add(
     ctx: Context<'_>,
-    #[string]
-    #[description = "Beatmap ID to check for a score."]
-    beatmap_url: Option<url::Url>,
+    #[description = "Gamemode to track."] new_mode: GameModeChoices,
+    #[description = "Alt account to change, 1 being your first alt."] alt: Option<usize>,
 ) -> Result<(), Error> {
     ctx.defer().await?;
     let connection = &mut ctx.data().db_pool.get().await?;
+    let Some(profile) = read_mode_account(ctx, connection, alt).await? else {
+        return Ok(());
+    };
 
-    let beatmap_info: BeatmapInfo;
-    let reply = get_reply(ctx);
-
-    if let Some(beatmap_url) = beatmap_url {
-        beatmap_info = get_beatmap_info(beatmap_url.as_str())?;
-        if beatmap_info.beatmapset_id.is_none() {
-            ctx.say("Please link to a beatmapset.").await?;
-            return Ok(());
-        }
-    } else if let Some(reply) = reply
-        && let Some(found_info) = find_beatmap_link(vec![reply]).await?
-    {
-        beatmap_info = found_info;
-    } else if let Some(found_info) = find_beatmap_link(
-        ctx.channel_id()
-            .messages(ctx.http(), GetMessages::new().limit(100))
-            .await?,
-    )
-    .await?
-    {
-        beatmap_info = found_info;
-    } else {
-        ctx.say("No beatmap link found.").await?;
+    let mode: GameMode = new_mode.into();
+    if profile.tracked_modes().contains(&mode) {
+        ctx.say(format!("Your {mode} scores are already tracked."))
+            .await?;
         return Ok(());
     }
 
-    let beatmapset = get_beatmapset(
-        connection,
+    let mut modes = profile.modes;
+    modes.push(Some(mode.to_string()));
+
+    let query_item = NewLinkedOsuProfile {
+        id: profile.id,
+        osu_id: profile.osu_id,
+        home_guild: profile.home_guild,
+        mode: profile.mode,
+        minimal_formatting: profile.minimal_formatting,
+        modes,
+        is_primary: profile.is_primary,
+        verified: profile.verified,
+    };
+    linked_osu_profiles::update(connection, profile.id, &query_item).await?;
+
+    add_profile_data(
         ctx.data().osu_client.clone(),
-        u32::try_from(
-            beatmap_info
-                .beatmapset_id
-                .ok_or("Failed to get beatmapset id in mapinfo command")?,
-        )?,
+        u32::try_from(profile.osu_id)?,
+        mode,
+        connection,
     )
     .await?;
 
-    let color = match ctx.author_member().await {
-        None => BLUE,
-        Some(member) => member.colour(ctx.cache()).unwrap_or(BLUE),
+    ctx.say(format!("Now tracking your {mode} scores.")).await?;
+
+    Ok(())
+}
+
+/// Stop tracking your scores in an osu! mode.
+#[poise::command(prefix_command, slash_command, category = "osu!", rename = "remove")]
+pub async fn mode_remove(
+    ctx: Context<'_>,
+    #[description = "Gamemode to stop tracking."] old_mode: GameModeChoices,
+    #[description = "Alt account to change, 1 being your first alt."] alt: Option<usize>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+    let connection = &mut ctx.data().db_pool.get().await?;
+    let Some(profile) = read_mode_account(ctx, connection, alt).await? else {
+        return Ok(());
     };
 
-    let embed = format_map_status(beatmapset, color)?;
+    let mode: GameMode = old_mode.into();
+    let tracked_modes = profile.tracked_modes();
+    if !tracked_modes.contains(&mode) {
+        ctx.say(format!("Your {mode} scores aren't tracked."))
+            .await?;
+        return Ok(());
+    }
 
-    let builder = CreateReply::default().embed(embed);
+    let Some(remaining_mode) = tracked_modes.into_iter().find(|tracked| *tracked != mode) else {
+        ctx.say("You need to track at least one mode.").await?;
+        return Ok(());
+    };
 
-    ctx.send(builder).await?;
+    // Commands default to the first mode left if the removed one was the default.
+    let default_mode = if profile.mode == mode.to_string() {
+        remaining_mode.to_string()
+    } else {
+        profile.mode
+    };
+
+    let query_item = NewLinkedOsuProfile {
+        id: profile.id,
+        osu_id: profile.osu_id,
+        home_guild: profile.home_guild,
+        mode: default_mode,
+        minimal_formatting: profile.minimal_formatting,
+        modes: profile
+            .modes
+            .into_iter()
+            .filter(|tracked| tracked.as_deref() != Some(mode.to_string().as_str()))
+            .collect(),
+        is_primary: profile.is_primary,
+        verified: profile.verified,
+    };
+    linked_osu_profiles::update(connection, profile.id, &query_item).await?;
+    osu_users::delete_mode(connection, profile.osu_id, &mode.to_string()).await?;
+
+    ctx.say(format!("Stopped tracking your {mode} scores."))
+        .await?;
 
     Ok(())
 }
 
-/// Display your score on a beatmap.
-#[poise::command(prefix_command, slash_command, category = "osu!", aliases("c"))]
-pub async fn score(
+/// Change the osu! mode commands use by default.
+#[poise::command(prefix_command, slash_command, category = "osu!", rename = "default")]
+pub async fn mode_default(
     ctx: Context<'_>,
-    #[string]
-    #[description = "Beatmap ID to check for a score."]
-    beatmap_url: Option<url::Url>,
-    #[description = "Discord user to check score for."] discord_user: Option<
-        poise::serenity_prelude::User,
-    >,
-    #[rest]
-    #[description = "osu! user to see score for."]
-    user: Option<String>,
+    #[description = "Gamemode to switch to."] new_mode: GameModeChoices,
+    #[description = "Alt account to change, 1 being your first alt."] alt: Option<usize>,
+) -> Result<(), Error> {
+    set_default_mode(ctx, new_mode, alt).await
+}
+
+async fn set_default_mode(
+    ctx: Context<'_>,
+    new_mode: GameModeChoices,
+    alt: Option<usize>,
 ) -> Result<(), Error> {
     ctx.defer().await?;
     let connection = &mut ctx.data().db_pool.get().await?;
-
-    let discord_user = discord_user.as_ref().unwrap_or_else(|| ctx.author());
-
-    let Some(osu_user) = get_user(ctx, discord_user, user, connection, None).await? else {
+    let Some(profile) = read_mode_account(ctx, connection, alt).await? else {
         return Ok(());
     };
 
-    let beatmap_info: BeatmapInfo;
-    let reply = get_reply(ctx);
+    let mode: GameMode = new_mode.into();
+    let newly_tracked = !profile.tracked_modes().contains(&mode);
 
-    if let Some(beatmap_url) = beatmap_url {
-        beatmap_info = get_beatmap_info(beatmap_url.as_str())?;
-        let Some(_) = beatmap_info.beatmap_id else {
-            ctx.say("Please link to a specific beatmap difficulty.")
-                .await?;
-            return Ok(());
-        };
+    let mut modes = profile.modes;
+    if newly_tracked {
+        modes.push(Some(mode.to_string()));
+    }
+
+    let query_item = NewLinkedOsuProfile {
+        id: profile.id,
+        osu_id: profile.osu_id,
+        home_guild: profile.home_guild,
+        mode: mode.to_string(),
+        minimal_formatting: profile.minimal_formatting,
+        modes,
+        is_primary: profile.is_primary,
+        verified: profile.verified,
+    };
+    linked_osu_profiles::update(connection, profile.id, &query_item).await?;
+
+    if newly_tracked {
+        add_profile_data(
+            ctx.data().osu_client.clone(),
+            u32::try_from(profile.osu_id)?,
+            mode,
+            connection,
+        )
+        .await?;
+    }
+
+    ctx.say(format!("Updated your osu! mode to {mode}."))
+        .await?;
+
+    Ok(())
+}
+
+/// List the osu! modes you're tracked in.
+#[poise::command(prefix_command, slash_command, category = "osu!", rename = "list")]
+pub async fn mode_list(
+    ctx: Context<'_>,
+    #[description = "Alt account to list the modes of, 1 being your first alt."] alt: Option<usize>,
+) -> Result<(), Error> {
+    let connection = &mut ctx.data().db_pool.get().await?;
+    let Some(profile) = read_mode_account(ctx, connection, alt).await? else {
+        return Ok(());
+    };
+
+    let formatted_modes = profile
+        .tracked_modes()
+        .iter()
+        .map(|mode| {
+            if mode.to_string() == profile.mode {
+                format!("**{mode}** (default)")
+            } else {
+                format!("**{mode}**")
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    ctx.say(format!("Tracked modes: {formatted_modes}")).await?;
+
+    Ok(())
+}
+
+/// Display your score on a beatmap.
+#[poise::command(prefix_command, slash_command, category = "osu!", aliases("c"))]
+pub async fn mapinfo(
+    ctx: Context<'_>,
+    #[string]
+    #[description = "Beatmap ID to check for a score."]
+    beatmap_url: Option<url::Url>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+    let connection = &mut ctx.data().db_pool.get().await?;
+
+    let Some(beatmap_info) = get_command_beatmap(ctx, beatmap_url, false).await? else {
+        return Ok(());
+    };
+
+    let beatmapset = get_beatmapset(
+        connection,
+        ctx.data().osu_client.clone(),
+        u32::try_from(
+            beatmap_info
+                .beatmapset_id
+                .ok_or("Failed to get beatmapset id in mapinfo command")?,
+        )?,
+    )
+    .await?;
+
+    let color = match ctx.author_member().await {
+        None => BLUE,
+        Some(member) => member.colour(ctx.cache()).unwrap_or(BLUE),
+    };
+
+    let embed = format_map_status(beatmapset, color)?;
+
+    let builder = CreateReply::default().embed(embed);
+
+    ctx.send(builder).await?;
+
+    Ok(())
+}
+
+/// Display your score on a beatmap.
+#[poise::command(prefix_command, slash_command, category = "osu!", aliases("c"))]
+pub async fn score(
+    ctx: Context<'_>,
+    #[string]
+    #[description = "Beatmap ID to check for a score."]
+    beatmap_url: Option<url::Url>,
+    #[description = "Discord user to check score for."] discord_user: Option<
+        poise::serenity_prelude::User,
+    >,
+    #[rest]
+    #[description = "osu! user to see score for."]
+    user: Option<String>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+    let connection = &mut ctx.data().db_pool.get().await?;
+
+    let discord_user = discord_user.as_ref().unwrap_or_else(|| ctx.author());
+
+    let Some(osu_user) = get_user(ctx, discord_user, user, connection, None).await? else {
+        return Ok(());
+    };
+
+    let beatmap_info: BeatmapInfo;
+    let reply = get_reply(ctx);
+
+    if let Some(beatmap_url) = beatmap_url {
+        beatmap_info = get_beatmap_info(beatmap_url.as_str())?;
+        let Some(_) = beatmap_info.beatmap_id else {
+            ctx.say("Please link to a specific beatmap difficulty.")
+                .await?;
+            return Ok(());
+        };
     } else if let Some(reply) = reply {
         if let Some(found_info) = find_beatmap_link(vec![reply]).await? {
             beatmap_info = found_info;
@@ -485,6 +945,202 @@ pub async fn score(
     Ok(())
 }
 
+/// Analyze an uploaded osu! replay.
+#[poise::command(prefix_command, slash_command, category = "osu!")]
+pub async fn replay(
+    ctx: Context<'_>,
+    #[description = "Replay file (.osr) to analyze."] replay_file: Attachment,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    if !replay_file.filename.to_lowercase().ends_with(".osr") {
+        ctx.say("Please attach an .osr replay file.").await?;
+        return Ok(());
+    }
+
+    let replay = match parse_replay(&replay_file.download().await?) {
+        Ok(replay) => replay,
+        Err(why) => {
+            ctx.say(format!("Failed to parse replay. {why}")).await?;
+            return Ok(());
+        }
+    };
+
+    let connection = &mut ctx.data().db_pool.get().await?;
+
+    let beatmap = match get_beatmap_by_checksum(
+        connection,
+        ctx.data().osu_client.clone(),
+        &replay.beatmap_md5,
+    )
+    .await
+    {
+        Ok(beatmap) => beatmap,
+        Err(why) => {
+            ctx.say(format!("Failed to find the replay's beatmap. {why}"))
+                .await?;
+            return Ok(());
+        }
+    };
+
+    let calculated_results = calculate_replay(&replay, &beatmap.2)?;
+
+    let map = rosu_pp::Beatmap::from_bytes(&beatmap.2.file)?;
+    let hit_windows = replay.hit_windows(&map);
+    let hit_errors = replay.hit_errors(&map, &hit_windows);
+    let unstable_rate = unstable_rate(&hit_errors, calculated_results.clock_rate);
+
+    let color = match ctx.author_member().await {
+        None => BLUE,
+        Some(member) => member.colour(ctx.cache()).unwrap_or(BLUE),
+    };
+
+    let description = format!(
+        "{}<t:{}:R>",
+        format_replay(&replay, &calculated_results, unstable_rate)?,
+        replay.timestamp.timestamp()
+    );
+
+    let mut embed = CreateEmbed::new()
+        .title(format!(
+            "{} - {} [{}]",
+            beatmap.1.artist, beatmap.1.title, beatmap.0.version
+        ))
+        .url(format_beatmap_link(
+            Some(beatmap.0.id),
+            beatmap.1.id,
+            Some(&replay.mode.to_string()),
+        ))
+        .thumbnail(
+            beatmap.1.list_cover.as_str(),
+            Some("The osu map's background".into()),
+        )
+        .color(color)
+        .description(description)
+        .author(CreateEmbedAuthor::new(format!(
+            "Replay by {}",
+            replay.player_name
+        )));
+
+    let mut builder = CreateReply::default();
+    if !hit_errors.is_empty() {
+        let analysis = render_replay_analysis(&replay, &hit_errors, &hit_windows, unstable_rate)?
+            .encode_png()?;
+        embed = embed.image(
+            "attachment://replay.png",
+            Some("The replay's hit errors and cursor heatmap".into()),
+        );
+        builder = builder.attachment(CreateAttachment::bytes(analysis, "replay.png"));
+    }
+
+    ctx.send(builder.embed(embed)).await?;
+
+    Ok(())
+}
+
+/// Calculate pp for a hypothetical score on a beatmap.
+#[poise::command(prefix_command, slash_command, category = "osu!", aliases("sim"))]
+pub async fn simulate(
+    ctx: Context<'_>,
+    #[string]
+    #[description = "Beatmap to simulate a score on."]
+    beatmap_url: Option<url::Url>,
+    #[description = "Mods to simulate, e.g. +HDDTCL."] mods: Option<String>,
+    #[description = "Accuracy to simulate."] accuracy: Option<f64>,
+    #[description = "Amount of misses."] misses: Option<u32>,
+    #[description = "Max combo reached."] combo: Option<u32>,
+    #[description = "Amount of 300s (greats/fruits)."] n300: Option<u32>,
+    #[description = "Amount of 100s (goods/droplets)."] n100: Option<u32>,
+    #[description = "Amount of 50s (tiny droplets in catch)."] n50: Option<u32>,
+    #[description = "Mode to simulate in, for converted beatmaps."] mode: Option<GameModeChoices>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+    let connection = &mut ctx.data().db_pool.get().await?;
+
+    let Some(beatmap_info) = get_command_beatmap(ctx, beatmap_url, true).await? else {
+        return Ok(());
+    };
+
+    let beatmap = get_beatmap(
+        connection,
+        ctx.data().osu_client.clone(),
+        u32::try_from(
+            beatmap_info
+                .beatmap_id
+                .ok_or("Failed to get beatmap ID in simulate command")?,
+        )?,
+    )
+    .await?;
+
+    let beatmap_mode =
+        gamemode_from_string(&beatmap.0.mode).ok_or("Failed to parse beatmap mode")?;
+    let mode = match mode {
+        Some(mode) => mode.into(),
+        None => beatmap_info.mode.unwrap_or(beatmap_mode),
+    };
+    if beatmap_mode != GameMode::Osu && mode != beatmap_mode {
+        ctx.say("Only osu!standard beatmaps can be converted to other modes.")
+            .await?;
+        return Ok(());
+    }
+
+    if let Some(accuracy) = accuracy
+        && !(0.0..=100.0).contains(&accuracy)
+    {
+        ctx.say("Accuracy must be between 0 and 100.").await?;
+        return Ok(());
+    }
+
+    let mods = mods
+        .unwrap_or_default()
+        .trim_start_matches('+')
+        .parse::<GameModsIntermode>()?
+        .with_mode(mode);
+
+    let simulated = SimulatedScore {
+        mods: mods.clone().into(),
+        acc: accuracy,
+        n300,
+        n100,
+        n50,
+        nmiss: misses,
+        combo,
+    };
+
+    let calculated_results = calculate_simulated(&beatmap.2, mode, &simulated)?;
+
+    let color = match ctx.author_member().await {
+        None => BLUE,
+        Some(member) => member.colour(ctx.cache()).unwrap_or(BLUE),
+    };
+
+    let embed = CreateEmbed::new()
+        .title(format!(
+            "{} - {} [{}]",
+            beatmap.1.artist, beatmap.1.title, beatmap.0.version
+        ))
+        .url(format_beatmap_link(
+            Some(beatmap.0.id),
+            beatmap.1.id,
+            Some(&mode.to_string()),
+        ))
+        .thumbnail(
+            beatmap.1.list_cover.as_str(),
+            Some("The osu map's background".into()),
+        )
+        .color(color)
+        .description(format_simulated_score(
+            &simulated,
+            mode,
+            &fmt_with_settings(&mods)?,
+            &calculated_results,
+        )?);
+
+    ctx.send(CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
 /// Display a list of your scores on a beatmap.
 #[poise::command(prefix_command, slash_command, category = "osu!")]
 pub async fn scores(
@@ -978,90 +1634,1502 @@ pub async fn top(
     Ok(())
 }
 
-#[poise::command(
-    prefix_command,
-    slash_command,
-    category = "osu!",
-    guild_only,
-    required_permissions = "MANAGE_GUILD"
-)]
-pub async fn score_notifications(
+/// Calculate how much pp a single new play needs to reach a target total.
+#[poise::command(prefix_command, slash_command, category = "osu!")]
+pub async fn ppneeded(
     ctx: Context<'_>,
-    #[description = "Channel to notify scores in"] score_channels: Vec<GuildChannel>,
+    #[description = "Total pp to reach."] target: f64,
+    #[description = "Mode to calculate for."] mode: Option<GameModeChoices>,
+    #[description = "Discord user to calculate for."] discord_user: Option<
+        poise::serenity_prelude::User,
+    >,
+    #[rest]
+    #[description = "User to calculate for."]
+    user: Option<String>,
 ) -> Result<(), Error> {
     ctx.defer().await?;
-    let guild = ctx
-        .guild()
-        .ok_or("Failed to get guild in score_notifications command")?
-        .clone();
+    let connection = &mut ctx.data().db_pool.get().await?;
 
-    let mut new_score_channels = Vec::new();
-    for score_channel in score_channels {
-        new_score_channels.push(Some(i64::try_from(score_channel.id.get())?));
+    let discord_user = discord_user.as_ref().unwrap_or_else(|| ctx.author());
+
+    let Some(osu_user) = get_user(ctx, discord_user, user, connection, mode).await? else {
+        return Ok(());
+    };
+
+    let total_pp = f64::from(
+        osu_user
+            .statistics
+            .as_ref()
+            .ok_or("Failed to get user statistics in ppneeded command")?
+            .pp,
+    );
+
+    if target <= total_pp {
+        ctx.say(format!(
+            "{} already has {}pp.",
+            osu_user.username,
+            remove_trailing_zeros(total_pp, 2)?
+        ))
+        .await?;
+        return Ok(());
     }
 
-    let connection = &mut ctx.data().db_pool.get().await?;
-    let new_item = match osu_guild_channels::read(connection, i64::try_from(guild.id.get())?).await
+    let mut best_scores = match ctx
+        .data()
+        .osu_client
+        .user_scores(osu_user.user_id)
+        .best()
+        .mode(osu_user.mode)
+        .limit(100)
+        .await
     {
-        Ok(guild_config) => NewOsuGuildChannel {
-            guild_id: guild_config.guild_id,
-            score_channel: Some(new_score_channels),
-            map_channel: guild_config.map_channel,
-        },
-        Err(_) => NewOsuGuildChannel {
-            guild_id: i64::try_from(guild.id.get())?,
-            score_channel: Some(new_score_channels),
-            map_channel: None,
-        },
+        Ok(best_scores) => best_scores,
+        Err(why) => {
+            ctx.say(format!("Failed to get best scores. {why}")).await?;
+            return Ok(());
+        }
     };
 
-    osu_guild_channels::create(connection, &new_item).await?;
+    best_scores.sort_by(|a, b| b.pp.unwrap_or(0.0).total_cmp(&a.pp.unwrap_or(0.0)));
+    let top_pp = best_scores
+        .iter()
+        .map(|score| f64::from(score.pp.unwrap_or(0.0)))
+        .collect::<Vec<f64>>();
+
+    let message = match calculate_pp_needed(&top_pp, total_pp, target) {
+        Some((needed, position)) => format!(
+            "{} needs a **{}pp** play to reach {}pp. It would be their #{} top play.",
+            osu_user.username,
+            remove_trailing_zeros(needed, 2)?,
+            remove_trailing_zeros(target, 2)?,
+            position + 1
+        ),
+        None => format!(
+            "{} can't reach {}pp with a single new play.",
+            osu_user.username,
+            remove_trailing_zeros(target, 2)?
+        ),
+    };
 
-    ctx.say("Updated your guild's score notification channel!")
-        .await?;
+    ctx.say(message).await?;
 
     Ok(())
 }
 
-#[poise::command(
-    prefix_command,
-    slash_command,
-    category = "osu!",
-    guild_only,
-    required_permissions = "MANAGE_GUILD"
-)]
-pub async fn map_notifications(
+#[derive(poise::ChoiceParameter)]
+pub enum HistoryPeriodChoices {
+    Week,
+    Month,
+    #[name = "3 months"]
+    #[name = "90 days"]
+    ThreeMonths,
+    Year,
+    #[name = "All"]
+    #[name = "All time"]
+    All,
+}
+
+impl HistoryPeriodChoices {
+    fn days(&self) -> i64 {
+        match self {
+            HistoryPeriodChoices::Week => 7,
+            HistoryPeriodChoices::Month => 30,
+            HistoryPeriodChoices::ThreeMonths => 90,
+            HistoryPeriodChoices::Year => 365,
+            HistoryPeriodChoices::All => 36500,
+        }
+    }
+}
+
+/// Display a graph of your pp, rank and accuracy over time.
+#[poise::command(prefix_command, slash_command, category = "osu!")]
+pub async fn history(
     ctx: Context<'_>,
-    #[description = "Channel to notify maps in"] map_channels: Vec<GuildChannel>,
+    #[description = "Period to show history for."] period: Option<HistoryPeriodChoices>,
+    #[description = "Mode to see history in."] mode: Option<GameModeChoices>,
+    #[description = "Discord user to see history for."] discord_user: Option<
+        poise::serenity_prelude::User,
+    >,
+    #[rest]
+    #[description = "User to see history for."]
+    user: Option<String>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+    let connection = &mut ctx.data().db_pool.get().await?;
+
+    let discord_user = discord_user.as_ref().unwrap_or_else(|| ctx.author());
+
+    let Some(osu_user) = get_user(ctx, discord_user, user, connection, mode).await? else {
+        return Ok(());
+    };
+
+    let period = period.unwrap_or(HistoryPeriodChoices::ThreeMonths);
+    let since = Utc::now() - chrono::Duration::days(period.days());
+
+    let snapshots = osu_user_snapshots::get_since(
+        connection,
+        i64::from(osu_user.user_id),
+        &osu_user.mode.to_string(),
+        since,
+    )
+    .await?;
+
+    let mut series = HistorySeries::default();
+    let source = if snapshots.len() > 1 {
+        for snapshot in &snapshots {
+            let timestamp = snapshot.time_taken.timestamp() as f64;
+            series.pp.push((timestamp, snapshot.pp));
+            series.accuracy.push((timestamp, snapshot.accuracy));
+            if snapshot.global_rank > 0 {
+                series
+                    .global_rank
+                    .push((timestamp, f64::from(snapshot.global_rank)));
+            }
+        }
+        "Tracked snapshots"
+    } else {
+        let rank_history = osu_user.rank_history.clone().unwrap_or_default();
+        let today = Utc::now().timestamp();
+        for (days_ago, rank) in rank_history.iter().rev().enumerate() {
+            let timestamp = today - i64::try_from(days_ago)? * 86_400;
+            if *rank > 0 && timestamp >= since.timestamp() {
+                series
+                    .global_rank
+                    .push((timestamp as f64, f64::from(*rank)));
+            }
+        }
+        series.global_rank.reverse();
+        "osu! rank history"
+    };
+
+    let graph = match render_history(
+        &format!("{} ({})", osu_user.username, osu_user.mode),
+        &series,
+    ) {
+        Ok(graph) => graph.encode_png()?,
+        Err(why) => {
+            ctx.say(format!(
+                "Couldn't draw history for {}. {why}",
+                osu_user.username
+            ))
+            .await?;
+            return Ok(());
+        }
+    };
+
+    let color = match ctx.author_member().await {
+        None => BLUE,
+        Some(member) => member.colour(ctx.cache()).unwrap_or(BLUE),
+    };
+
+    let embed = CreateEmbed::new()
+        .image(
+            "attachment://history.png",
+            Some("The user's pp, rank and accuracy history".into()),
+        )
+        .color(color)
+        .author(
+            CreateEmbedAuthor::new(osu_user.username.to_string())
+                .icon_url(osu_user.avatar_url.clone())
+                .url(format_user_link(i64::from(osu_user.user_id))),
+        )
+        .footer(CreateEmbedFooter::new(format!("Source: {source}")));
+
+    ctx.send(
+        CreateReply::default()
+            .embed(embed)
+            .attachment(CreateAttachment::bytes(graph, "history.png")),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Display a leaderboard of the linked osu! users in this server.
+#[poise::command(prefix_command, slash_command, category = "osu!", guild_only)]
+pub async fn leaderboard(
+    ctx: Context<'_>,
+    #[description = "What to rank users by."] sort_type: Option<LeaderboardSortChoices>,
+    #[description = "Mode to rank users in."] mode: Option<GameModeChoices>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+    let connection = &mut ctx.data().db_pool.get().await?;
+
+    let mode = match mode {
+        Some(mode) => mode.into(),
+        None => match linked_osu_profiles::read(connection, i64::try_from(ctx.author().id.get())?)
+            .await
+        {
+            Ok(profile) => gamemode_from_string(&profile.mode).unwrap_or(GameMode::Osu),
+            Err(_) => GameMode::Osu,
+        },
+    };
+    let sort_type = sort_type.unwrap_or(LeaderboardSortChoices::Pp);
+
+    let guild_profiles = get_guild_profiles(ctx, connection).await?;
+
+    let mut entries = Vec::new();
+    for profile in guild_profiles {
+        if let Ok(osu_user) = osu_users::read(connection, profile.osu_id, &mode.to_string()).await {
+            entries.push(LeaderboardEntry::from_osu_user(profile.id, &osu_user));
+            continue;
+        }
+
+        let osu_user = match ctx
+            .data()
+            .osu_client
+            .user(u32::try_from(profile.osu_id)?)
+            .mode(mode)
+            .await
+        {
+            Ok(mut osu_user) => {
+                osu_user.mode = mode;
+                NewOsuUser::try_from(osu_user)?
+            }
+            Err(why) => {
+                error!(
+                    "Failed to get user {} for leaderboard: {}",
+                    profile.osu_id, why
+                );
+                continue;
+            }
+        };
+        entries.push(LeaderboardEntry::from_new_osu_user(profile.id, &osu_user));
+    }
+
+    if entries.is_empty() {
+        ctx.say("No linked osu! users found in this server.")
+            .await?;
+        return Ok(());
+    }
+
+    sort_leaderboard(&mut entries, sort_type);
+
+    let guild_name = ctx
+        .guild()
+        .map_or_else(|| String::from("Server"), |guild| guild.name.to_string());
+
+    send_list_embed(
+        ctx,
+        format_leaderboard(&entries, sort_type)?,
+        10,
+        format!("{guild_name} osu! leaderboard ({mode})"),
+        None,
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Display the best scores on a beatmap by linked osu! users in this server.
+#[poise::command(prefix_command, slash_command, category = "osu!", guild_only)]
+pub async fn serverscores(
+    ctx: Context<'_>,
+    #[string]
+    #[description = "Beatmap to see server scores for."]
+    beatmap_url: Option<url::Url>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+    let connection = &mut ctx.data().db_pool.get().await?;
+
+    let Some(beatmap_info) = get_command_beatmap(ctx, beatmap_url, true).await? else {
+        return Ok(());
+    };
+
+    let beatmap_id = u32::try_from(
+        beatmap_info
+            .beatmap_id
+            .ok_or("Failed to get beatmap ID in serverscores command")?,
+    )?;
+
+    let beatmap = get_beatmap(connection, ctx.data().osu_client.clone(), beatmap_id).await?;
+
+    let mode = match beatmap_info.mode {
+        Some(mode) => mode,
+        None => gamemode_from_string(&beatmap.0.mode)
+            .ok_or("Failed to parse beatmap mode in serverscores command")?,
+    };
+
+    let guild_profiles = get_guild_profiles(ctx, connection).await?;
+
+    let mut server_scores = Vec::new();
+    for profile in guild_profiles {
+        let Ok(score) = ctx
+            .data()
+            .osu_client
+            .beatmap_user_score(beatmap_id, u32::try_from(profile.osu_id)?)
+            .mode(mode)
+            .await
+        else {
+            continue;
+        };
+
+        let pp = calculate(
+            Some(&score.score),
+            &beatmap.0,
+            &beatmap.2,
+            calculate_potential_acc(&score.score),
+        )?;
+
+        let username = match osu_users::read(connection, profile.osu_id, &profile.mode).await {
+            Ok(osu_user) => osu_user.username,
+            Err(_) => profile.osu_id.to_string(),
+        };
+
+        server_scores.push(ServerScore {
+            discord_id: profile.id,
+            osu_id: profile.osu_id,
+            username,
+            score: score.score,
+            pp,
+        });
+    }
+
+    if server_scores.is_empty() {
+        ctx.say("Nobody in this server has a score on this beatmap.")
+            .await?;
+        return Ok(());
+    }
+
+    server_scores.sort_by(|a, b| b.score.score.cmp(&a.score.score));
+
+    send_list_embed(
+        ctx,
+        format_server_scores(&server_scores)?,
+        5,
+        format!(
+            "{} - {} [{}]",
+            beatmap.1.artist, beatmap.1.title, beatmap.0.version
+        ),
+        Some(beatmap.1.list_cover.clone()),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Search the beatmaps cached by the bot.
+#[poise::command(prefix_command, slash_command, category = "osu!")]
+pub async fn search(
+    ctx: Context<'_>,
+    #[description = "Text to match against artist, title, creator or difficulty name."]
+    query: Option<String>,
+    #[description = "Mode to search in."] mode: Option<GameModeChoices>,
+    #[description = "Ranked status of the beatmap."] status: Option<MapStatusChoices>,
+    #[description = "Minimum star rating."] min_stars: Option<f64>,
+    #[description = "Maximum star rating."] max_stars: Option<f64>,
+    #[description = "Minimum approach rate."] min_ar: Option<f64>,
+    #[description = "Maximum approach rate."] max_ar: Option<f64>,
+    #[description = "Minimum overall difficulty."] min_od: Option<f64>,
+    #[description = "Maximum overall difficulty."] max_od: Option<f64>,
+    #[description = "Minimum circle size."] min_cs: Option<f64>,
+    #[description = "Maximum circle size."] max_cs: Option<f64>,
+    #[description = "Minimum BPM."] min_bpm: Option<f64>,
+    #[description = "Maximum BPM."] max_bpm: Option<f64>,
+    #[description = "Minimum drain length in seconds."] min_length: Option<i32>,
+    #[description = "Maximum drain length in seconds."] max_length: Option<i32>,
+    #[description = "Minimum object count."] min_objects: Option<i32>,
+    #[description = "Maximum object count."] max_objects: Option<i32>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+    let connection = &mut ctx.data().db_pool.get().await?;
+
+    let search = BeatmapSearch {
+        text: query.map(|query| query.trim().to_owned()),
+        mode: mode.map(|mode| GameMode::from(mode).to_string()),
+        status: status.map(|status| format_rank_status(RankStatus::from(status))),
+        min_stars,
+        max_stars,
+        min_ar,
+        max_ar,
+        min_od,
+        max_od,
+        min_cs,
+        max_cs,
+        min_bpm,
+        max_bpm,
+        min_length,
+        max_length,
+        min_objects,
+        max_objects,
+    };
+
+    let results = beatmaps::search(connection, &search, 100).await?;
+
+    if results.is_empty() {
+        ctx.say("No cached beatmaps match your search.").await?;
+        return Ok(());
+    }
+
+    let mut entries = Vec::new();
+    for (beatmap, beatmapset) in &results {
+        entries.push(format_search_result(beatmap, beatmapset)?);
+    }
+
+    send_list_embed(
+        ctx,
+        entries,
+        5,
+        format!("Beatmap search ({} results)", results.len()),
+        results
+            .first()
+            .map(|(_, beatmapset)| beatmapset.list_cover.clone()),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Display the strain graph of a beatmap.
+#[poise::command(prefix_command, slash_command, category = "osu!", aliases("strains"))]
+pub async fn graph(
+    ctx: Context<'_>,
+    #[string]
+    #[description = "Beatmap to draw the strains of."]
+    beatmap_url: Option<url::Url>,
+    #[description = "Mods to apply, e.g. +HDDT."] mods: Option<String>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+    let connection = &mut ctx.data().db_pool.get().await?;
+
+    let Some(beatmap_info) = get_command_beatmap(ctx, beatmap_url, true).await? else {
+        return Ok(());
+    };
+
+    let beatmap = get_beatmap(
+        connection,
+        ctx.data().osu_client.clone(),
+        u32::try_from(
+            beatmap_info
+                .beatmap_id
+                .ok_or("Failed to get beatmap ID in graph command")?,
+        )?,
+    )
+    .await?;
+
+    let beatmap_mode =
+        gamemode_from_string(&beatmap.0.mode).ok_or("Failed to parse beatmap mode")?;
+    // Only osu!standard beatmaps can be converted, so links to other modes are ignored otherwise.
+    let mode = if beatmap_mode == GameMode::Osu {
+        beatmap_info.mode.unwrap_or(beatmap_mode)
+    } else {
+        beatmap_mode
+    };
+
+    let mods = mods
+        .unwrap_or_default()
+        .trim_start_matches('+')
+        .parse::<GameModsIntermode>()?
+        .with_mode(mode);
+
+    let strains = get_map_strains(&beatmap.2.file, mode, &mods.clone().into())?;
+
+    let title = if mods.is_empty() {
+        format!("{} [{}]", beatmap.1.title, beatmap.0.version)
+    } else {
+        format!("{} [{}] +{mods}", beatmap.1.title, beatmap.0.version)
+    };
+
+    let graph = match render_strains(&title, &strains) {
+        Ok(graph) => graph.encode_png()?,
+        Err(why) => {
+            ctx.say(format!("Couldn't draw the strain graph. {why}"))
+                .await?;
+            return Ok(());
+        }
+    };
+
+    let color = match ctx.author_member().await {
+        None => BLUE,
+        Some(member) => member.colour(ctx.cache()).unwrap_or(BLUE),
+    };
+
+    let embed = CreateEmbed::new()
+        .title(format!(
+            "{} - {} [{}]",
+            beatmap.1.artist, beatmap.1.title, beatmap.0.version
+        ))
+        .url(format_beatmap_link(
+            Some(beatmap.0.id),
+            beatmap.1.id,
+            Some(&mode.to_string()),
+        ))
+        .image(
+            "attachment://graph.png",
+            Some("The strains of the beatmap over time".into()),
+        )
+        .color(color);
+
+    ctx.send(
+        CreateReply::default()
+            .embed(embed)
+            .attachment(CreateAttachment::bytes(graph, "graph.png")),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Draw the playfield of a beatmap at an editor timestamp.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "osu!",
+    aliases("snapshot", "pf")
+)]
+pub async fn playfield(
+    ctx: Context<'_>,
+    #[string]
+    #[description = "Beatmap to draw the playfield of."]
+    beatmap_url: Option<url::Url>,
+    #[rest]
+    #[description = "Timestamp or editor link, e.g. 01:23:456 (1,2,3) -"]
+    timestamp: String,
+) -> Result<(), Error> {
+    let Some(time) = get_editor_timestamp(&timestamp) else {
+        ctx.say("Please give a timestamp like `01:23:456`.").await?;
+        return Ok(());
+    };
+
+    ctx.defer().await?;
+    let connection = &mut ctx.data().db_pool.get().await?;
+
+    let Some(beatmap_info) = get_command_beatmap(ctx, beatmap_url, true).await? else {
+        return Ok(());
+    };
+
+    let beatmap = get_beatmap(
+        connection,
+        ctx.data().osu_client.clone(),
+        u32::try_from(
+            beatmap_info
+                .beatmap_id
+                .ok_or("Failed to get beatmap ID in playfield command")?,
+        )?,
+    )
+    .await?;
+
+    if gamemode_from_string(&beatmap.0.mode) != Some(GameMode::Osu) {
+        ctx.say("Only osu!standard beatmaps can be drawn.").await?;
+        return Ok(());
+    }
+
+    let (mut embed, snapshot) = create_snapshot(&beatmap, time)?;
+
+    if let Some(member) = ctx.author_member().await
+        && let Some(color) = member.colour(ctx.cache())
+    {
+        embed = embed.color(color);
+    }
+
+    ctx.send(
+        CreateReply::default()
+            .embed(embed)
+            .attachment(CreateAttachment::bytes(snapshot, "playfield.png")),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Recommend cached beatmaps based on your top plays.
+#[poise::command(prefix_command, slash_command, category = "osu!")]
+pub async fn recommend(
+    ctx: Context<'_>,
+    #[description = "What kind of maps to recommend."] recommend_type: Option<RecommendChoices>,
+    #[description = "Mode to recommend maps for."] mode: Option<GameModeChoices>,
+    #[description = "Discord user to recommend maps for."] discord_user: Option<
+        poise::serenity_prelude::User,
+    >,
+    #[rest]
+    #[description = "User to recommend maps for."]
+    user: Option<String>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+    let connection = &mut ctx.data().db_pool.get().await?;
+
+    let discord_user = discord_user.as_ref().unwrap_or_else(|| ctx.author());
+    let recommend_type = recommend_type.unwrap_or(RecommendChoices::Farm);
+
+    let Some(osu_user) = get_user(ctx, discord_user, user, connection, mode).await? else {
+        return Ok(());
+    };
+
+    let api_scores = ctx
+        .data()
+        .osu_client
+        .user_scores(osu_user.user_id)
+        .best()
+        .mode(osu_user.mode)
+        .limit(100)
+        .await?;
+
+    let best_scores = set_up_score_list(&ctx, connection, api_scores).await?;
+
+    let Some(profile) = build_skill_profile(&best_scores) else {
+        ctx.say(format!("No top scores found for {}.", osu_user.username))
+            .await?;
+        return Ok(());
+    };
+
+    let played_map_ids = best_scores
+        .iter()
+        .map(|score| score.2.id)
+        .collect::<HashSet<i64>>();
+
+    let recommendations = get_recommendations(
+        connection,
+        &profile,
+        &osu_user.mode.to_string(),
+        recommend_type,
+        &played_map_ids,
+    )
+    .await?;
+
+    if recommendations.is_empty() {
+        ctx.say("No cached beatmaps match your skill profile yet.")
+            .await?;
+        return Ok(());
+    }
+
+    let mut entries = Vec::new();
+    for (beatmap, beatmapset) in &recommendations {
+        entries.push(format_search_result(beatmap, beatmapset)?);
+    }
+
+    send_list_embed(
+        ctx,
+        entries,
+        5,
+        format!(
+            "{} maps for {} ({})",
+            recommend_type.name(),
+            osu_user.username,
+            format_skill_profile(&profile)?
+        ),
+        Some(osu_user.avatar_url.clone()),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Multiplayer lobby commands.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "osu!",
+    rename = "match",
+    subcommands("track", "cost")
+)]
+pub async fn osu_match(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Post the results of a multiplayer lobby in this channel as games finish.
+#[poise::command(prefix_command, slash_command, category = "osu!")]
+pub async fn track(
+    ctx: Context<'_>,
+    #[description = "Lobby ID or match link."] lobby: String,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let Some(match_id) = get_match_id(&lobby) else {
+        ctx.say("Please provide a lobby ID or a link to the match.")
+            .await?;
+        return Ok(());
+    };
+
+    // The match is claimed before the lookup, so running the command twice can't start two
+    // trackers for it.
+    let tracked_matches = TRACKED_MATCHES.get_or_init(DashMap::new);
+    let already_tracked = match tracked_matches.entry(match_id) {
+        Entry::Occupied(_) => true,
+        Entry::Vacant(entry) => {
+            entry.insert(ctx.channel_id());
+            false
+        }
+    };
+
+    if already_tracked {
+        ctx.say("This match is already being tracked.").await?;
+        return Ok(());
+    }
+
+    let Ok(osu_match) = ctx.data().osu_client.osu_match(match_id).await else {
+        tracked_matches.remove(&match_id);
+        ctx.say("Match not found.").await?;
+        return Ok(());
+    };
+
+    if osu_match.match_info.end_time.is_some() {
+        tracked_matches.remove(&match_id);
+        ctx.say("This match has already closed.").await?;
+        return Ok(());
+    }
+
+    let mut match_tracker = MatchTracker::new(
+        ctx.serenity_context().http.clone(),
+        ctx.data().osu_client.clone(),
+        ctx.data().db_pool.clone(),
+        match_id,
+        ctx.channel_id(),
+    );
+
+    tokio::spawn(async move {
+        match match_tracker.tracking_loop().await {
+            Ok(()) => {}
+            Err(why) => error!("{why}"),
+        }
+    });
+
+    ctx.say(format!(
+        "Now tracking **{}**, games will be posted here as they finish.",
+        osu_match.match_info.name
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Calculate the match cost of every player in a finished multiplayer match.
+#[poise::command(prefix_command, slash_command, category = "osu!")]
+pub async fn cost(
+    ctx: Context<'_>,
+    #[description = "Lobby ID or match link."] lobby: String,
+    #[description = "Number of warmup maps to exclude from the start."] warmups: Option<usize>,
+    #[description = "First map to include, counted after warmups."] from: Option<usize>,
+    #[description = "Last map to include, counted after warmups."] to: Option<usize>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let Some(match_id) = get_match_id(&lobby) else {
+        ctx.say("Please provide a lobby ID or a link to the match.")
+            .await?;
+        return Ok(());
+    };
+
+    let Ok((osu_match, users)) = get_full_match(&ctx.data().osu_client, match_id).await else {
+        ctx.say("Match not found.").await?;
+        return Ok(());
+    };
+
+    let from = from.unwrap_or(1).max(1);
+    let games = finished_games(&osu_match)
+        .skip(warmups.unwrap_or(0))
+        .enumerate()
+        .filter(|(index, _)| index + 1 >= from && to.is_none_or(|to| index + 1 <= to))
+        .map(|(_, game)| game)
+        .collect::<Vec<&MatchGame>>();
+
+    if games.is_empty() {
+        ctx.say("No finished maps in the given range.").await?;
+        return Ok(());
+    }
+
+    let match_costs = calculate_match_costs(&games);
+    if match_costs.is_empty() {
+        ctx.say("No scores were set in the given range.").await?;
+        return Ok(());
+    }
+
+    send_list_embed(
+        ctx,
+        format_match_costs(&match_costs, &users)?,
+        10,
+        format!(
+            "Match costs for {} ({} maps)",
+            osu_match.match_info.name,
+            games.len()
+        ),
+        None,
+    )
+    .await?;
+
+    Ok(())
+}
+
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "osu!",
+    guild_only,
+    required_permissions = "MANAGE_GUILD"
+)]
+pub async fn score_notifications(
+    ctx: Context<'_>,
+    #[description = "Channel to notify scores in"] score_channels: Vec<GuildChannel>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+    let guild = ctx
+        .guild()
+        .ok_or("Failed to get guild in score_notifications command")?
+        .clone();
+
+    let mut new_score_channels = Vec::new();
+    for score_channel in score_channels {
+        new_score_channels.push(Some(i64::try_from(score_channel.id.get())?));
+    }
+
+    let connection = &mut ctx.data().db_pool.get().await?;
+    let new_item = match osu_guild_channels::read(connection, i64::try_from(guild.id.get())?).await
+    {
+        Ok(guild_config) => NewOsuGuildChannel {
+            guild_id: guild_config.guild_id,
+            score_channel: Some(new_score_channels),
+            map_channel: guild_config.map_channel,
+            require_verified: guild_config.require_verified,
+            timestamp_channel: guild_config.timestamp_channel,
+        },
+        Err(_) => NewOsuGuildChannel {
+            guild_id: i64::try_from(guild.id.get())?,
+            score_channel: Some(new_score_channels),
+            map_channel: None,
+            require_verified: false,
+            timestamp_channel: None,
+        },
+    };
+
+    osu_guild_channels::create(connection, &new_item).await?;
+
+    ctx.say("Updated your guild's score notification channel!")
+        .await?;
+
+    Ok(())
+}
+
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "osu!",
+    guild_only,
+    required_permissions = "MANAGE_GUILD"
+)]
+pub async fn map_notifications(
+    ctx: Context<'_>,
+    #[description = "Channel to notify maps in"] map_channels: Vec<GuildChannel>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+    let guild = ctx
+        .guild()
+        .ok_or("Failed to get guild in map_notifications command")?
+        .clone();
+
+    let mut new_map_channels = Vec::new();
+    for map_channel in map_channels {
+        new_map_channels.push(Some(i64::try_from(map_channel.id.get())?));
+    }
+    let connection = &mut ctx.data().db_pool.get().await?;
+    let new_item = match osu_guild_channels::read(connection, i64::try_from(guild.id.get())?).await
+    {
+        Ok(guild_config) => NewOsuGuildChannel {
+            guild_id: guild_config.guild_id,
+            score_channel: guild_config.score_channel,
+            map_channel: Some(new_map_channels),
+            require_verified: guild_config.require_verified,
+            timestamp_channel: guild_config.timestamp_channel,
+        },
+        Err(_) => NewOsuGuildChannel {
+            guild_id: i64::try_from(guild.id.get())?,
+            score_channel: None,
+            map_channel: Some(new_map_channels),
+            require_verified: false,
+            timestamp_channel: None,
+        },
+    };
+
+    osu_guild_channels::create(connection, &new_item).await?;
+
+    ctx.say("Updated your guild's map notification channel!")
+        .await?;
+
+    Ok(())
+}
+
+/// Draw the playfield whenever an editor timestamp is posted in these channels.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "osu!",
+    guild_only,
+    required_permissions = "MANAGE_GUILD"
+)]
+pub async fn timestamp_channels(
+    ctx: Context<'_>,
+    #[description = "Channels to respond to editor timestamps in"] timestamp_channels: Vec<
+        GuildChannel,
+    >,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+    let guild = ctx
+        .guild()
+        .ok_or("Failed to get guild in timestamp_channels command")?
+        .clone();
+
+    let mut new_timestamp_channels = Vec::new();
+    for timestamp_channel in timestamp_channels {
+        new_timestamp_channels.push(Some(i64::try_from(timestamp_channel.id.get())?));
+    }
+    let connection = &mut ctx.data().db_pool.get().await?;
+    let new_item = match osu_guild_channels::read(connection, i64::try_from(guild.id.get())?).await
+    {
+        Ok(guild_config) => NewOsuGuildChannel {
+            guild_id: guild_config.guild_id,
+            score_channel: guild_config.score_channel,
+            map_channel: guild_config.map_channel,
+            require_verified: guild_config.require_verified,
+            timestamp_channel: Some(new_timestamp_channels),
+        },
+        Err(_) => NewOsuGuildChannel {
+            guild_id: i64::try_from(guild.id.get())?,
+            score_channel: None,
+            map_channel: None,
+            require_verified: false,
+            timestamp_channel: Some(new_timestamp_channels),
+        },
+    };
+
+    osu_guild_channels::create(connection, &new_item).await?;
+
+    ctx.say("Updated your guild's editor timestamp channels!")
+        .await?;
+
+    Ok(())
+}
+
+/// Choose where your scores and map events get posted.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "osu!",
+    subcommands(
+        "notifications_mute",
+        "notifications_unmute",
+        "notifications_pause",
+        "notifications_resume",
+        "notifications_dm"
+    )
+)]
+pub async fn notifications(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Stop posting your scores and map events in this server.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "osu!",
+    guild_only,
+    rename = "mute"
+)]
+pub async fn notifications_mute(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = i64::try_from(
+        ctx.guild_id()
+            .ok_or("Failed to get guild ID in notifications_mute command")?
+            .get(),
+    )?;
+
+    let connection = &mut ctx.data().db_pool.get().await?;
+    let mut item = osu_notification_preferences::read_or_default(
+        connection,
+        i64::try_from(ctx.author().id.get())?,
+    )
+    .await?;
+
+    if !item.muted_guilds.contains(&Some(guild_id)) {
+        item.muted_guilds.push(Some(guild_id));
+    }
+    osu_notification_preferences::create(connection, &item).await?;
+
+    ctx.say("Your scores and map events won't be posted in this server anymore.")
+        .await?;
+
+    Ok(())
+}
+
+/// Post your scores and map events in this server again.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "osu!",
+    guild_only,
+    rename = "unmute"
+)]
+pub async fn notifications_unmute(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = i64::try_from(
+        ctx.guild_id()
+            .ok_or("Failed to get guild ID in notifications_unmute command")?
+            .get(),
+    )?;
+
+    let connection = &mut ctx.data().db_pool.get().await?;
+    let mut item = osu_notification_preferences::read_or_default(
+        connection,
+        i64::try_from(ctx.author().id.get())?,
+    )
+    .await?;
+
+    item.muted_guilds
+        .retain(|muted_guild| *muted_guild != Some(guild_id));
+    osu_notification_preferences::create(connection, &item).await?;
+
+    ctx.say("Your scores and map events will be posted in this server again.")
+        .await?;
+
+    Ok(())
+}
+
+/// Pause the tracking of your scores and map events.
+#[poise::command(prefix_command, slash_command, category = "osu!", rename = "pause")]
+pub async fn notifications_pause(
+    ctx: Context<'_>,
+    #[description = "Days to pause tracking for."]
+    #[min = 1]
+    #[max = 3650]
+    days: u32,
+) -> Result<(), Error> {
+    // Prefix commands don't enforce the limits above.
+    let Some(paused_until) = (1..=3650)
+        .contains(&days)
+        .then(|| Utc::now().checked_add_signed(chrono::Duration::days(i64::from(days))))
+        .flatten()
+    else {
+        ctx.say("Tracking can be paused for 1 to 3650 days.")
+            .await?;
+        return Ok(());
+    };
+
+    let connection = &mut ctx.data().db_pool.get().await?;
+    let mut item = osu_notification_preferences::read_or_default(
+        connection,
+        i64::try_from(ctx.author().id.get())?,
+    )
+    .await?;
+
+    item.paused_until = Some(paused_until);
+    osu_notification_preferences::create(connection, &item).await?;
+
+    ctx.say(format!(
+        "Tracking paused until <t:{}:f>.",
+        paused_until.timestamp()
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Resume the tracking of your scores and map events.
+#[poise::command(prefix_command, slash_command, category = "osu!", rename = "resume")]
+pub async fn notifications_resume(ctx: Context<'_>) -> Result<(), Error> {
+    let connection = &mut ctx.data().db_pool.get().await?;
+    let mut item = osu_notification_preferences::read_or_default(
+        connection,
+        i64::try_from(ctx.author().id.get())?,
+    )
+    .await?;
+
+    item.paused_until = None;
+    osu_notification_preferences::create(connection, &item).await?;
+
+    ctx.say("Tracking resumed!").await?;
+
+    Ok(())
+}
+
+/// Get your scores and map events in your DMs instead of in servers.
+#[poise::command(prefix_command, slash_command, category = "osu!", rename = "dm")]
+pub async fn notifications_dm(
+    ctx: Context<'_>,
+    #[description = "Whether to send notifications by DM."] enabled: bool,
+) -> Result<(), Error> {
+    let connection = &mut ctx.data().db_pool.get().await?;
+    let mut item = osu_notification_preferences::read_or_default(
+        connection,
+        i64::try_from(ctx.author().id.get())?,
+    )
+    .await?;
+
+    item.direct_message = enabled;
+    osu_notification_preferences::create(connection, &item).await?;
+
+    if enabled {
+        ctx.say("Your notifications will be sent by DM.").await?;
+    } else {
+        ctx.say("Your notifications will be posted in servers again.")
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Set which scores get posted in one of the score notification channels.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "osu!",
+    guild_only,
+    required_permissions = "MANAGE_GUILD"
+)]
+pub async fn score_rules(
+    ctx: Context<'_>,
+    #[description = "Score notification channel to set rules for."] score_channel: GuildChannel,
+    #[description = "Only post plays within this position of a user's top 100."]
+    #[min = 1]
+    #[max = 100]
+    top_n: Option<i32>,
+    #[description = "Only post plays worth at least this much pp."] min_pp: Option<f64>,
+    #[description = "Post plays on unranked and loved maps. Defaults to true."]
+    include_unranked: Option<bool>,
+    #[description = "Post the pp and rank changes of a play. Defaults to true."] show_diff: Option<
+        bool,
+    >,
+    #[rest]
+    #[description = "Space separated modes to post plays from. Defaults to all modes."]
+    modes: Option<String>,
 ) -> Result<(), Error> {
     ctx.defer().await?;
-    let guild = ctx
-        .guild()
-        .ok_or("Failed to get guild in map_notifications command")?
-        .clone();
+    let guild_id = ctx
+        .guild_id()
+        .ok_or("Failed to get guild ID in score_rules command")?;
 
-    let mut new_map_channels = Vec::new();
-    for map_channel in map_channels {
-        new_map_channels.push(Some(i64::try_from(map_channel.id.get())?));
+    // Prefix commands don't enforce the limits above.
+    if top_n.is_some_and(|top_n| !(1..=100).contains(&top_n)) {
+        ctx.say("The top play position has to be between 1 and 100.")
+            .await?;
+        return Ok(());
+    }
+
+    let modes = match modes {
+        Some(modes) => {
+            let mut parsed_modes = Vec::new();
+            for mode in modes.split_whitespace() {
+                let Some(parsed_mode) = gamemode_from_string(mode) else {
+                    ctx.say(format!("Unknown mode `{mode}`.")).await?;
+                    return Ok(());
+                };
+                parsed_modes.push(Some(parsed_mode.to_string()));
+            }
+            Some(parsed_modes).filter(|parsed_modes| !parsed_modes.is_empty())
+        }
+        None => None,
+    };
+
+    let connection = &mut ctx.data().db_pool.get().await?;
+    let guild_id = i64::try_from(guild_id.get())?;
+    let channel_id = i64::try_from(score_channel.id.get())?;
+
+    let is_score_channel = osu_guild_channels::read(connection, guild_id)
+        .await
+        .is_ok_and(|guild_config| {
+            guild_config
+                .score_channel
+                .is_some_and(|channels| channels.contains(&Some(channel_id)))
+        });
+
+    if !is_score_channel {
+        ctx.say(format!(
+            "<#{}> isn't a score notification channel, add it with `osu score_notifications` first.",
+            score_channel.id
+        ))
+        .await?;
+        return Ok(());
     }
+
+    let item = NewOsuScoreRule {
+        guild_id,
+        channel_id,
+        top_n,
+        min_pp,
+        modes,
+        include_unranked: include_unranked.unwrap_or(true),
+        show_diff: show_diff.unwrap_or(true),
+    };
+
+    osu_score_rules::create(connection, &item).await?;
+
+    ctx.say(format!(
+        "Updated the score notification rules for <#{}>!",
+        score_channel.id
+    ))
+    .await?;
+
+    Ok(())
+}
+
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "osu!",
+    guild_only,
+    required_permissions = "MANAGE_GUILD"
+)]
+pub async fn delete_score_rules(
+    ctx: Context<'_>,
+    #[description = "Score notification channel to remove the rules of."]
+    score_channel: GuildChannel,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+    let guild_id = ctx
+        .guild_id()
+        .ok_or("Failed to get guild ID in delete_score_rules command")?;
+
     let connection = &mut ctx.data().db_pool.get().await?;
-    let new_item = match osu_guild_channels::read(connection, i64::try_from(guild.id.get())?).await
+    if osu_score_rules::delete(
+        connection,
+        i64::try_from(guild_id.get())?,
+        i64::try_from(score_channel.id.get())?,
+    )
+    .await?
+        > 0
     {
-        Ok(guild_config) => NewOsuGuildChannel {
-            guild_id: guild_config.guild_id,
-            score_channel: guild_config.score_channel,
-            map_channel: Some(new_map_channels),
-        },
-        Err(_) => NewOsuGuildChannel {
-            guild_id: i64::try_from(guild.id.get())?,
-            score_channel: None,
-            map_channel: Some(new_map_channels),
+        ctx.say(format!(
+            "Removed the score notification rules for <#{}>.",
+            score_channel.id
+        ))
+        .await?;
+    } else {
+        ctx.say("That channel doesn't have any rules set.").await?;
+    }
+
+    Ok(())
+}
+
+/// Post newly qualified, ranked and loved beatmapsets from all of osu! in the map channels.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "osu!",
+    guild_only,
+    required_permissions = "MANAGE_GUILD"
+)]
+pub async fn map_feed(
+    ctx: Context<'_>,
+    #[description = "Post qualified beatmapsets. Defaults to true."] qualified: Option<bool>,
+    #[description = "Post ranked beatmapsets. Defaults to true."] ranked: Option<bool>,
+    #[description = "Post loved beatmapsets. Defaults to true."] loved: Option<bool>,
+    #[description = "Only post beatmapsets with difficulties in this mode."] mode: Option<
+        GameModeChoices,
+    >,
+    #[description = "Minimum star rating of a difficulty."] min_stars: Option<f64>,
+    #[description = "Maximum star rating of a difficulty."] max_stars: Option<f64>,
+    #[description = "Maximum drain length of a difficulty in seconds."] max_length: Option<i32>,
+    #[rest]
+    #[description = "Only post beatmapsets with one of these space separated tags."]
+    tags: Option<String>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+    let guild_id = ctx
+        .guild_id()
+        .ok_or("Failed to get guild ID in map_feed command")?;
+
+    let connection = &mut ctx.data().db_pool.get().await?;
+    let guild_id = i64::try_from(guild_id.get())?;
+
+    let has_map_channel = osu_guild_channels::read(connection, guild_id)
+        .await
+        .is_ok_and(|guild_config| {
+            guild_config
+                .map_channel
+                .is_some_and(|channels| channels.iter().flatten().next().is_some())
+        });
+
+    let item = NewOsuMapFeed {
+        guild_id,
+        qualified: qualified.unwrap_or(true),
+        ranked: ranked.unwrap_or(true),
+        loved: loved.unwrap_or(true),
+        mode: mode.map(|mode| GameMode::from(mode).to_string()),
+        min_stars,
+        max_stars,
+        max_length,
+        tags: tags.filter(|tags| !tags.trim().is_empty()),
+    };
+
+    osu_map_feeds::create(connection, &item).await?;
+
+    let mut response = String::from("Updated your guild's map feed!");
+    if !has_map_channel {
+        response.push_str(
+            "\nThis server has no map notification channel yet, set one with `osu map_notifications`.",
+        );
+    }
+
+    ctx.say(response).await?;
+
+    Ok(())
+}
+
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "osu!",
+    guild_only,
+    required_permissions = "MANAGE_GUILD"
+)]
+pub async fn delete_map_feed(ctx: Context<'_>) -> Result<(), Error> {
+    ctx.defer().await?;
+    let guild_id = ctx
+        .guild_id()
+        .ok_or("Failed to get guild ID in delete_map_feed command")?;
+
+    let connection = &mut ctx.data().db_pool.get().await?;
+    if osu_map_feeds::delete(connection, i64::try_from(guild_id.get())?).await? > 0 {
+        ctx.say("Your guild's map feed has been deleted.").await?;
+    } else {
+        ctx.say("Your guild doesn't have a map feed set up.")
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Follow the mapping activity of osu! users in this server.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "osu!",
+    guild_only,
+    subcommands("follow_mapper", "follow_list", "follow_remove")
+)]
+pub async fn follow(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Post a mapper's uploads, updates and rank status changes in the map notification channel.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "osu!",
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    rename = "mapper"
+)]
+pub async fn follow_mapper(
+    ctx: Context<'_>,
+    #[rest]
+    #[description = "osu! username of the mapper."]
+    mapper: String,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+    let guild_id = ctx
+        .guild_id()
+        .ok_or("Failed to get guild ID in follow_mapper command")?;
+
+    let Ok(user) = ctx.data().osu_client.user(mapper.as_str()).await else {
+        ctx.say("Could not find user.").await?;
+        return Ok(());
+    };
+
+    let connection = &mut ctx.data().db_pool.get().await?;
+    let guild_id = i64::try_from(guild_id.get())?;
+
+    let has_map_channel = osu_guild_channels::read(connection, guild_id)
+        .await
+        .is_ok_and(|guild_config| {
+            guild_config
+                .map_channel
+                .is_some_and(|channels| channels.iter().flatten().next().is_some())
+        });
+
+    followed_mappers::create(
+        connection,
+        &NewFollowedMapper {
+            guild_id,
+            mapper_id: i64::from(user.user_id),
+            username: user.username.to_string(),
         },
+    )
+    .await?;
+
+    let mut response = format!("Now following **{}**'s mapping activity!", user.username);
+    if !has_map_channel {
+        response.push_str(
+            "\nThis server has no map notification channel yet, set one with `osu map_notifications`.",
+        );
+    }
+
+    ctx.say(response).await?;
+
+    Ok(())
+}
+
+/// List the mappers followed in this server.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "osu!",
+    guild_only,
+    rename = "list"
+)]
+pub async fn follow_list(ctx: Context<'_>) -> Result<(), Error> {
+    ctx.defer().await?;
+    let guild_id = ctx
+        .guild_id()
+        .ok_or("Failed to get guild ID in follow_list command")?;
+
+    let connection = &mut ctx.data().db_pool.get().await?;
+    let follows = followed_mappers::get_guild(connection, i64::try_from(guild_id.get())?).await?;
+
+    if follows.is_empty() {
+        ctx.say("This server isn't following any mappers.").await?;
+        return Ok(());
+    }
+
+    send_list_embed(
+        ctx,
+        follows
+            .iter()
+            .map(|follow| {
+                format!(
+                    "[{}]({})",
+                    follow.username,
+                    format_user_link(follow.mapper_id)
+                )
+            })
+            .collect(),
+        15,
+        String::from("Followed mappers"),
+        None,
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Stop following a mapper in this server.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "osu!",
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    rename = "remove"
+)]
+pub async fn follow_remove(
+    ctx: Context<'_>,
+    #[rest]
+    #[description = "osu! username of the mapper."]
+    mapper: String,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+    let guild_id = ctx
+        .guild_id()
+        .ok_or("Failed to get guild ID in follow_remove command")?;
+
+    let connection = &mut ctx.data().db_pool.get().await?;
+    let follows = followed_mappers::get_guild(connection, i64::try_from(guild_id.get())?).await?;
+
+    let Some(follow) = follows
+        .iter()
+        .find(|follow| follow.username.eq_ignore_ascii_case(mapper.trim()))
+    else {
+        ctx.say("This server isn't following that mapper.").await?;
+        return Ok(());
     };
 
-    osu_guild_channels::create(connection, &new_item).await?;
+    followed_mappers::delete(connection, follow.guild_id, follow.mapper_id).await?;
 
-    ctx.say("Updated your guild's map notification channel!")
+    ctx.say(format!("Stopped following **{}**.", follow.username))
         .await?;
 
     Ok(())
@@ -1094,6 +3162,56 @@ pub async fn delete_guild_config(ctx: Context<'_>) -> Result<(), Error> {
     Ok(())
 }
 
+/// Only post scores of members who verified their osu! account.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "osu!",
+    guild_only,
+    required_permissions = "MANAGE_GUILD"
+)]
+pub async fn require_verification(
+    ctx: Context<'_>,
+    #[description = "Whether linked accounts need to be verified."] required: bool,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+    let guild_id = i64::try_from(
+        ctx.guild_id()
+            .ok_or("Failed to get guild ID in require_verification command")?
+            .get(),
+    )?;
+
+    let connection = &mut ctx.data().db_pool.get().await?;
+    let new_item = match osu_guild_channels::read(connection, guild_id).await {
+        Ok(guild_config) => NewOsuGuildChannel {
+            guild_id: guild_config.guild_id,
+            score_channel: guild_config.score_channel,
+            map_channel: guild_config.map_channel,
+            require_verified: required,
+            timestamp_channel: guild_config.timestamp_channel,
+        },
+        Err(_) => NewOsuGuildChannel {
+            guild_id,
+            score_channel: None,
+            map_channel: None,
+            require_verified: required,
+            timestamp_channel: None,
+        },
+    };
+
+    osu_guild_channels::create(connection, &new_item).await?;
+
+    if required {
+        ctx.say("Only scores from verified osu! accounts will be posted in this server.")
+            .await?;
+    } else {
+        ctx.say("Scores from all linked osu! accounts will be posted in this server.")
+            .await?;
+    }
+
+    Ok(())
+}
+
 #[poise::command(prefix_command, category = "osu!", owners_only)]
 pub async fn debug(ctx: Context<'_>) -> Result<(), Error> {
     ctx.defer().await?;
@@ -1104,6 +3222,7 @@ pub async fn debug(ctx: Context<'_>) -> Result<(), Error> {
     let beatmapsets_count = beatmapsets::count_entries(connection).await?;
     let guild_channels_count = osu_guild_channels::count_entries(connection).await?;
     let osu_file_count = osu_file::count_entries(connection).await?;
+    let osu_file_size = osu_file::total_size(connection).await?;
 
     let mut playing_users: Vec<String> = Vec::new();
     for linked_profile in &linked_profiles {
@@ -1140,7 +3259,7 @@ pub async fn debug(ctx: Context<'_>) -> Result<(), Error> {
          Total linked profiles: `{}`\n\
          Total beatmaps cached: `{}`\n\
          Total beatmapsets cached: `{}`\n\
-         Total osu files cached: `{}`\n\
+         Total osu files cached: `{}` (`{:.1} MB`)\n\
          Total guilds with configs: `{}`",
         formatted_playing_members,
         tracked_profiles.len(),
@@ -1148,6 +3267,7 @@ pub async fn debug(ctx: Context<'_>) -> Result<(), Error> {
         beatmaps_count,
         beatmapsets_count,
         osu_file_count,
+        osu_file_size as f64 / 1024.0 / 1024.0,
         guild_channels_count
     );
 
@@ -27,6 +27,8 @@ diesel::table! {
         #[max_length = 90]
         version -> Varchar,
         time_cached -> Timestamptz,
+        od -> Nullable<Float8>,
+        hp -> Nullable<Float8>,
     }
 }
 
@@ -53,13 +55,25 @@ diesel::table! {
 }
 
 diesel::table! {
-    linked_osu_profiles (id) {
+    followed_mappers (guild_id, mapper_id) {
+        guild_id -> Int8,
+        mapper_id -> Int8,
+        username -> Text,
+        last_event -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    linked_osu_profiles (id, osu_id) {
         id -> Int8,
         osu_id -> Int8,
         home_guild -> Int8,
         #[max_length = 7]
         mode -> Varchar,
         minimal_formatting -> Bool,
+        modes -> Array<Nullable<Varchar>>,
+        is_primary -> Bool,
+        verified -> Bool,
     }
 }
 
@@ -68,6 +82,7 @@ diesel::table! {
         id -> Int8,
         file -> Bytea,
         time_cached -> Timestamptz,
+        compressed -> Bool,
     }
 }
 
@@ -76,6 +91,41 @@ diesel::table! {
         guild_id -> Int8,
         score_channel -> Nullable<Array<Nullable<Int8>>>,
         map_channel -> Nullable<Array<Nullable<Int8>>>,
+        require_verified -> Bool,
+        timestamp_channel -> Nullable<Array<Nullable<Int8>>>,
+    }
+}
+
+diesel::table! {
+    osu_map_feed_cursors (status) {
+        #[max_length = 9]
+        status -> Varchar,
+        last_date -> Timestamptz,
+        last_ids -> Array<Nullable<Int8>>,
+    }
+}
+
+diesel::table! {
+    osu_map_feeds (guild_id) {
+        guild_id -> Int8,
+        qualified -> Bool,
+        ranked -> Bool,
+        loved -> Bool,
+        #[max_length = 7]
+        mode -> Nullable<Varchar>,
+        min_stars -> Nullable<Float8>,
+        max_stars -> Nullable<Float8>,
+        max_length -> Nullable<Int4>,
+        tags -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    osu_notification_preferences (id) {
+        id -> Int8,
+        muted_guilds -> Array<Nullable<Int8>>,
+        paused_until -> Nullable<Timestamptz>,
+        direct_message -> Bool,
     }
 }
 
@@ -88,7 +138,35 @@ diesel::table! {
 }
 
 diesel::table! {
-    osu_users (id) {
+    osu_score_rules (guild_id, channel_id) {
+        guild_id -> Int8,
+        channel_id -> Int8,
+        top_n -> Nullable<Int4>,
+        min_pp -> Nullable<Float8>,
+        modes -> Nullable<Array<Nullable<Varchar>>>,
+        include_unranked -> Bool,
+        show_diff -> Bool,
+    }
+}
+
+diesel::table! {
+    osu_user_snapshots (id) {
+        id -> Int8,
+        osu_id -> Int8,
+        #[max_length = 7]
+        mode -> Varchar,
+        pp -> Float8,
+        accuracy -> Float8,
+        global_rank -> Int4,
+        country_rank -> Int4,
+        playcount -> Int4,
+        ranked_score -> Int8,
+        time_taken -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    osu_users (id, mode) {
         id -> Int8,
         username -> Text,
         avatar_url -> Text,
@@ -105,6 +183,7 @@ diesel::table! {
         ticks -> Int4,
         time_cached -> Timestamptz,
         min_pp -> Float8,
+        playcount -> Int4,
     }
 }
 
@@ -151,10 +230,16 @@ diesel::joinable!(beatmaps -> osu_files (id));
 diesel::allow_tables_to_appear_in_same_query!(
     beatmaps,
     beatmapsets,
+    followed_mappers,
     linked_osu_profiles,
     osu_files,
     osu_guild_channels,
+    osu_map_feed_cursors,
+    osu_map_feeds,
+    osu_notification_preferences,
     osu_notifications,
+    osu_score_rules,
+    osu_user_snapshots,
     osu_users,
     prefix,
     questions,
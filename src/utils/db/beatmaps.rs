@@ -4,10 +4,12 @@ use crate::models::beatmapsets::Beatmapset;
 use crate::models::osu_files::OsuFile;
 use crate::schema::beatmapsets;
 use crate::schema::{beatmaps, osu_files};
+use crate::utils::db::osu_file;
 use diesel::dsl::count;
-use diesel::insert_into;
-use diesel::prelude::{ExpressionMethods, QueryDsl};
+use diesel::prelude::{BoolExpressionMethods, ExpressionMethods, QueryDsl};
+use diesel::result::Error::DeserializationError;
 use diesel::upsert::excluded;
+use diesel::{PgTextExpressionMethods, insert_into};
 use diesel_async::{AsyncPgConnection, RunQueryDsl};
 
 impl TryFrom<&rosu_v2::prelude::BeatmapExtended> for NewBeatmap {
@@ -35,6 +37,8 @@ impl TryFrom<&rosu_v2::prelude::BeatmapExtended> for NewBeatmap {
             total_length: i32::try_from(beatmap.seconds_total)?,
             user_id: i64::from(beatmap.creator_id),
             version: beatmap.version.clone(),
+            od: Some(f64::from(beatmap.od)),
+            hp: Some(f64::from(beatmap.hp)),
         })
     }
 }
@@ -56,8 +60,12 @@ pub async fn create(
         items.push(NewBeatmap::try_from(beatmap)?);
     }
 
+    upsert(db, &items).await
+}
+
+async fn upsert(db: &mut AsyncPgConnection, items: &[NewBeatmap]) -> Result<(), Error> {
     insert_into(beatmaps::table)
-        .values(&items)
+        .values(items)
         .on_conflict(beatmaps::id)
         .do_update()
         .set((
@@ -81,6 +89,8 @@ pub async fn create(
             beatmaps::total_length.eq(excluded(beatmaps::total_length)),
             beatmaps::user_id.eq(excluded(beatmaps::user_id)),
             beatmaps::version.eq(excluded(beatmaps::version)),
+            beatmaps::od.eq(excluded(beatmaps::od)),
+            beatmaps::hp.eq(excluded(beatmaps::hp)),
         ))
         .execute(db)
         .await?;
@@ -98,6 +108,146 @@ pub async fn get_single(
         .filter(beatmaps::id.eq(param_id))
         .first::<(Beatmap, Beatmapset, OsuFile)>(db)
         .await
+        .and_then(|(beatmap, beatmapset, osu_file)| {
+            Ok((
+                beatmap,
+                beatmapset,
+                osu_file::decompress(osu_file).map_err(DeserializationError)?,
+            ))
+        })
+}
+
+/// The difficulties of a beatmapset without their .osu files.
+pub async fn get_beatmapset(
+    db: &mut AsyncPgConnection,
+    param_beatmapset_id: i64,
+) -> Result<Vec<Beatmap>, diesel::result::Error> {
+    beatmaps::table
+        .filter(beatmaps::beatmapset_id.eq(param_beatmapset_id))
+        .load::<Beatmap>(db)
+        .await
+}
+
+pub async fn get_single_by_checksum(
+    db: &mut AsyncPgConnection,
+    param_checksum: &str,
+) -> Result<(Beatmap, Beatmapset, OsuFile), diesel::result::Error> {
+    beatmaps::table
+        .inner_join(beatmapsets::table)
+        .inner_join(osu_files::table)
+        .filter(beatmaps::checksum.eq(param_checksum))
+        .first::<(Beatmap, Beatmapset, OsuFile)>(db)
+        .await
+        .and_then(|(beatmap, beatmapset, osu_file)| {
+            Ok((
+                beatmap,
+                beatmapset,
+                osu_file::decompress(osu_file).map_err(DeserializationError)?,
+            ))
+        })
+}
+
+#[derive(Default)]
+pub struct BeatmapSearch {
+    pub text: Option<String>,
+    pub mode: Option<String>,
+    pub status: Option<String>,
+    pub min_stars: Option<f64>,
+    pub max_stars: Option<f64>,
+    pub min_ar: Option<f64>,
+    pub max_ar: Option<f64>,
+    pub min_od: Option<f64>,
+    pub max_od: Option<f64>,
+    pub min_cs: Option<f64>,
+    pub max_cs: Option<f64>,
+    pub min_bpm: Option<f64>,
+    pub max_bpm: Option<f64>,
+    pub min_length: Option<i32>,
+    pub max_length: Option<i32>,
+    pub min_objects: Option<i32>,
+    pub max_objects: Option<i32>,
+}
+
+pub async fn search(
+    db: &mut AsyncPgConnection,
+    search: &BeatmapSearch,
+    limit: i64,
+) -> Result<Vec<(Beatmap, Beatmapset)>, diesel::result::Error> {
+    let object_count = beatmaps::count_circles + beatmaps::count_sliders + beatmaps::count_spinners;
+
+    let mut query = beatmaps::table
+        .inner_join(beatmapsets::table)
+        .filter(beatmaps::convert.eq(false))
+        .into_boxed();
+
+    if let Some(text) = &search.text {
+        let pattern = format!("%{text}%");
+        query = query.filter(
+            beatmapsets::artist
+                .ilike(pattern.clone())
+                .or(beatmapsets::title.ilike(pattern.clone()))
+                .or(beatmapsets::creator.ilike(pattern.clone()))
+                .or(beatmaps::version.ilike(pattern)),
+        );
+    }
+    if let Some(mode) = &search.mode {
+        query = query.filter(beatmaps::mode.eq(mode.clone()));
+    }
+    if let Some(status) = &search.status {
+        query = query.filter(beatmaps::status.eq(status.clone()));
+    }
+    if let Some(min_stars) = search.min_stars {
+        query = query.filter(beatmaps::difficulty_rating.ge(min_stars));
+    }
+    if let Some(max_stars) = search.max_stars {
+        query = query.filter(beatmaps::difficulty_rating.le(max_stars));
+    }
+    if let Some(min_ar) = search.min_ar {
+        query = query.filter(beatmaps::ar.ge(min_ar));
+    }
+    if let Some(max_ar) = search.max_ar {
+        query = query.filter(beatmaps::ar.le(max_ar));
+    }
+    // Maps cached before OD was stored don't have it, so they're kept in rather than dropped.
+    if let Some(min_od) = search.min_od {
+        query = query.filter(beatmaps::od.is_null().or(beatmaps::od.ge(min_od)));
+    }
+    if let Some(max_od) = search.max_od {
+        query = query.filter(beatmaps::od.is_null().or(beatmaps::od.le(max_od)));
+    }
+    if let Some(min_cs) = search.min_cs {
+        query = query.filter(beatmaps::cs.ge(min_cs));
+    }
+    if let Some(max_cs) = search.max_cs {
+        query = query.filter(beatmaps::cs.le(max_cs));
+    }
+    if let Some(min_bpm) = search.min_bpm {
+        query = query.filter(beatmaps::bpm.ge(min_bpm));
+    }
+    if let Some(max_bpm) = search.max_bpm {
+        query = query.filter(beatmaps::bpm.le(max_bpm));
+    }
+    if let Some(min_length) = search.min_length {
+        query = query.filter(beatmaps::drain.ge(min_length));
+    }
+    if let Some(max_length) = search.max_length {
+        query = query.filter(beatmaps::drain.le(max_length));
+    }
+    if let Some(min_objects) = search.min_objects {
+        query = query.filter(object_count.ge(min_objects));
+    }
+    if let Some(max_objects) = search.max_objects {
+        query = query.filter(object_count.le(max_objects));
+    }
+
+    query
+        .order((
+            beatmapsets::play_count.desc(),
+            beatmaps::difficulty_rating.asc(),
+        ))
+        .limit(limit)
+        .load::<(Beatmap, Beatmapset)>(db)
+        .await
 }
 
 pub async fn delete(db: &mut AsyncPgConnection, param_id: i64) -> Result<(), Error> {
@@ -107,3 +257,71 @@ pub async fn delete(db: &mut AsyncPgConnection, param_id: i64) -> Result<(), Err
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use diesel_async::{AsyncConnection, AsyncMigrationHarness};
+    use diesel_migrations::MigrationHarness;
+
+    fn new_beatmap(hp: f64) -> NewBeatmap {
+        NewBeatmap {
+            id: -1,
+            ar: 9.0,
+            beatmapset_id: -1,
+            checksum: None,
+            max_combo: 100,
+            bpm: 180.0,
+            convert: false,
+            count_circles: 50,
+            count_sliders: 25,
+            count_spinners: 0,
+            cs: 4.0,
+            difficulty_rating: 5.0,
+            drain: 60,
+            mode: String::from("osu"),
+            passcount: 0,
+            playcount: 0,
+            status: String::from("Ranked"),
+            total_length: 60,
+            user_id: -1,
+            version: String::from("Insane"),
+            od: Some(8.0),
+            hp: Some(hp),
+        }
+    }
+
+    // Needs a database to run against, so it's skipped when DATABASE_URL isn't set.
+    #[tokio::test(flavor = "multi_thread")]
+    async fn recaching_updates_hp() {
+        let Ok(database_url) = std::env::var("DATABASE_URL") else {
+            return;
+        };
+
+        let mut harness = AsyncMigrationHarness::new(
+            AsyncPgConnection::establish(&database_url)
+                .await
+                .expect("Could not connect to the database"),
+        );
+        harness
+            .run_pending_migrations(crate::MIGRATIONS)
+            .expect("Couldn't run migrations");
+        drop(harness);
+
+        let db = &mut AsyncPgConnection::establish(&database_url)
+            .await
+            .expect("Could not connect to the database");
+        db.begin_test_transaction().await.unwrap();
+
+        upsert(db, &[new_beatmap(5.0)]).await.unwrap();
+        upsert(db, &[new_beatmap(7.5)]).await.unwrap();
+
+        let hp = beatmaps::table
+            .find(-1_i64)
+            .select(beatmaps::hp)
+            .first::<Option<f64>>(db)
+            .await
+            .unwrap();
+        assert_eq!(hp, Some(7.5));
+    }
+}
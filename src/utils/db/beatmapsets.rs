@@ -3,6 +3,7 @@ use crate::models::beatmaps::Beatmap;
 use crate::models::beatmapsets::{Beatmapset, NewBeatmapset};
 use crate::models::osu_files::OsuFile;
 use crate::schema::{beatmapsets, osu_files};
+use crate::utils::db::osu_file;
 use diesel::dsl::count;
 use diesel::prelude::{BelongingToDsl, ExpressionMethods, QueryDsl};
 use diesel::{SelectableHelper, insert_into};
@@ -51,6 +52,8 @@ pub async fn count_entries(db: &mut AsyncPgConnection) -> Result<i64, Error> {
         .await?)
 }
 
+/// Reads a cached beatmapset with all of its beatmaps. Sets with evicted .osu files count as
+/// not cached, so they get fetched again as a whole.
 pub async fn read(
     db: &mut AsyncPgConnection,
     param_id: i64,
@@ -64,8 +67,20 @@ pub async fn read(
         let beatmaps = Beatmap::belonging_to(&beatmapset)
             .inner_join(osu_files::table)
             .select((Beatmap::as_select(), OsuFile::as_select()))
-            .load(db)
+            .load::<(Beatmap, OsuFile)>(db)
+            .await?
+            .into_iter()
+            .map(|(beatmap, file)| Ok((beatmap, osu_file::decompress(file)?)))
+            .collect::<Result<Vec<(Beatmap, OsuFile)>, Error>>()?;
+
+        let beatmap_count = Beatmap::belonging_to(&beatmapset)
+            .count()
+            .get_result::<i64>(db)
             .await?;
+        if i64::try_from(beatmaps.len())? != beatmap_count {
+            return Ok(None);
+        }
+
         return Ok(Some((beatmapset, beatmaps)));
     }
     Ok(None)
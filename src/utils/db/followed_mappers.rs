@@ -0,0 +1,66 @@
+use crate::models::followed_mappers::{FollowedMapper, NewFollowedMapper};
+use crate::schema::followed_mappers;
+use diesel::insert_into;
+use diesel::prelude::{ExpressionMethods, QueryDsl, QueryResult};
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+
+pub async fn create(
+    db: &mut AsyncPgConnection,
+    item: &NewFollowedMapper,
+) -> QueryResult<FollowedMapper> {
+    insert_into(followed_mappers::table)
+        .values(item)
+        .on_conflict((followed_mappers::guild_id, followed_mappers::mapper_id))
+        .do_update()
+        .set(followed_mappers::username.eq(&item.username))
+        .get_result::<FollowedMapper>(db)
+        .await
+}
+
+pub async fn get_all(db: &mut AsyncPgConnection) -> QueryResult<Vec<FollowedMapper>> {
+    followed_mappers::table
+        .order(followed_mappers::mapper_id)
+        .load::<FollowedMapper>(db)
+        .await
+}
+
+pub async fn get_guild(
+    db: &mut AsyncPgConnection,
+    param_guild_id: i64,
+) -> QueryResult<Vec<FollowedMapper>> {
+    followed_mappers::table
+        .filter(followed_mappers::guild_id.eq(param_guild_id))
+        .order(followed_mappers::username)
+        .load::<FollowedMapper>(db)
+        .await
+}
+
+pub async fn update_last_event(
+    db: &mut AsyncPgConnection,
+    param_guild_id: i64,
+    param_mapper_id: i64,
+    param_last_event: chrono::DateTime<chrono::Utc>,
+) -> QueryResult<usize> {
+    diesel::update(
+        followed_mappers::table
+            .filter(followed_mappers::guild_id.eq(param_guild_id))
+            .filter(followed_mappers::mapper_id.eq(param_mapper_id)),
+    )
+    .set(followed_mappers::last_event.eq(param_last_event))
+    .execute(db)
+    .await
+}
+
+pub async fn delete(
+    db: &mut AsyncPgConnection,
+    param_guild_id: i64,
+    param_mapper_id: i64,
+) -> QueryResult<usize> {
+    diesel::delete(
+        followed_mappers::table
+            .filter(followed_mappers::guild_id.eq(param_guild_id))
+            .filter(followed_mappers::mapper_id.eq(param_mapper_id)),
+    )
+    .execute(db)
+    .await
+}
@@ -1,15 +1,35 @@
 use crate::Error;
 use crate::models::linked_osu_profiles::{LinkedOsuProfile, NewLinkedOsuProfile};
+use crate::utils::osu::misc::gamemode_from_string;
 use diesel::insert_into;
 use diesel::prelude::{ExpressionMethods, QueryDsl, QueryResult};
 use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use rosu_v2::prelude::GameMode;
+
+impl LinkedOsuProfile {
+    /// The modes scores and profile changes are tracked in. `mode` is the one commands default
+    /// to, and is always tracked.
+    pub fn tracked_modes(&self) -> Vec<GameMode> {
+        let mut modes = vec![self.mode.clone()];
+        for mode in self.modes.iter().flatten() {
+            if !modes.contains(mode) {
+                modes.push(mode.clone());
+            }
+        }
+
+        modes
+            .iter()
+            .filter_map(|mode| gamemode_from_string(mode))
+            .collect()
+    }
+}
 
 pub async fn create(db: &mut AsyncPgConnection, item: &NewLinkedOsuProfile) -> Result<(), Error> {
-    use crate::schema::linked_osu_profiles::dsl::{id, linked_osu_profiles};
+    use crate::schema::linked_osu_profiles::dsl::{id, linked_osu_profiles, osu_id};
 
     insert_into(linked_osu_profiles)
         .values(item)
-        .on_conflict(id)
+        .on_conflict((id, osu_id))
         .do_update()
         .set(item)
         .execute(db)
@@ -18,15 +38,45 @@ pub async fn create(db: &mut AsyncPgConnection, item: &NewLinkedOsuProfile) -> R
     Ok(())
 }
 
+/// Reads the primary account of a Discord user.
 pub async fn read(db: &mut AsyncPgConnection, param_id: i64) -> QueryResult<LinkedOsuProfile> {
-    use crate::schema::linked_osu_profiles::dsl::{id, linked_osu_profiles};
+    use crate::schema::linked_osu_profiles::dsl::{id, is_primary, linked_osu_profiles};
+
+    linked_osu_profiles
+        .filter(id.eq(param_id))
+        .filter(is_primary.eq(true))
+        .first::<LinkedOsuProfile>(db)
+        .await
+}
+
+pub async fn read_account(
+    db: &mut AsyncPgConnection,
+    param_id: i64,
+    param_osu_id: i64,
+) -> QueryResult<LinkedOsuProfile> {
+    use crate::schema::linked_osu_profiles::dsl::{id, linked_osu_profiles, osu_id};
 
     linked_osu_profiles
         .filter(id.eq(param_id))
+        .filter(osu_id.eq(param_osu_id))
         .first::<LinkedOsuProfile>(db)
         .await
 }
 
+/// Every account linked by a Discord user, starting with the primary one.
+pub async fn get_accounts(
+    db: &mut AsyncPgConnection,
+    param_id: i64,
+) -> Result<Vec<LinkedOsuProfile>, Error> {
+    use crate::schema::linked_osu_profiles::dsl::{id, is_primary, linked_osu_profiles, osu_id};
+
+    Ok(linked_osu_profiles
+        .filter(id.eq(param_id))
+        .order((is_primary.desc(), osu_id.asc()))
+        .load::<LinkedOsuProfile>(db)
+        .await?)
+}
+
 pub async fn get_all(db: &mut AsyncPgConnection) -> Result<Vec<LinkedOsuProfile>, Error> {
     use crate::schema::linked_osu_profiles::dsl::linked_osu_profiles;
 
@@ -38,16 +88,72 @@ pub async fn update(
     param_id: i64,
     item: &NewLinkedOsuProfile,
 ) -> Result<(), Error> {
-    use crate::schema::linked_osu_profiles::dsl::{id, linked_osu_profiles};
+    use crate::schema::linked_osu_profiles::dsl::{id, linked_osu_profiles, osu_id};
+
+    diesel::update(
+        linked_osu_profiles
+            .filter(id.eq(param_id))
+            .filter(osu_id.eq(item.osu_id)),
+    )
+    .set(item)
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
+
+/// Makes `param_osu_id` the primary account of a Discord user, and every other one an alt.
+pub async fn set_primary(
+    db: &mut AsyncPgConnection,
+    param_id: i64,
+    param_osu_id: i64,
+) -> Result<(), Error> {
+    use crate::schema::linked_osu_profiles::dsl::{id, is_primary, linked_osu_profiles, osu_id};
 
     diesel::update(linked_osu_profiles.filter(id.eq(param_id)))
-        .set(item)
+        .set(is_primary.eq(osu_id.eq(param_osu_id)))
         .execute(db)
         .await?;
 
     Ok(())
 }
 
+pub async fn set_verified(
+    db: &mut AsyncPgConnection,
+    param_id: i64,
+    param_osu_id: i64,
+) -> Result<(), Error> {
+    use crate::schema::linked_osu_profiles::dsl::{id, linked_osu_profiles, osu_id, verified};
+
+    diesel::update(
+        linked_osu_profiles
+            .filter(id.eq(param_id))
+            .filter(osu_id.eq(param_osu_id)),
+    )
+    .set(verified.eq(true))
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn delete_account(
+    db: &mut AsyncPgConnection,
+    param_id: i64,
+    param_osu_id: i64,
+) -> QueryResult<usize> {
+    use crate::schema::linked_osu_profiles::dsl::{id, linked_osu_profiles, osu_id};
+
+    diesel::delete(
+        linked_osu_profiles
+            .filter(id.eq(param_id))
+            .filter(osu_id.eq(param_osu_id)),
+    )
+    .execute(db)
+    .await
+}
+
+/// Removes every account linked by a Discord user.
 pub async fn delete(db: &mut AsyncPgConnection, param_id: i64) -> QueryResult<usize> {
     use crate::schema::linked_osu_profiles::dsl::{id, linked_osu_profiles};
 
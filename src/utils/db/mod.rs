@@ -1,10 +1,16 @@
 pub mod beatmaps;
 pub mod beatmapsets;
 pub mod establish_connection;
+pub mod followed_mappers;
 pub mod linked_osu_profiles;
 pub mod osu_file;
 pub mod osu_guild_channels;
+pub mod osu_map_feed_cursors;
+pub mod osu_map_feeds;
+pub mod osu_notification_preferences;
 pub mod osu_notifications;
+pub mod osu_score_rules;
+pub mod osu_user_snapshots;
 pub mod osu_users;
 pub mod prefix;
 pub mod questions;
@@ -1,22 +1,66 @@
 use crate::Error;
-use crate::models::osu_files::NewOsuFile;
+use crate::models::osu_files::{NewOsuFile, OsuFile};
 use crate::schema::osu_files;
-use diesel::dsl::count;
+use chrono::{Duration, Utc};
+use diesel::dsl::{count, now, sql};
 use diesel::prelude::QueryDsl;
+use diesel::sql_types::{Array, BigInt, Nullable};
 use diesel::upsert::excluded;
-use diesel::{ExpressionMethods, insert_into};
+use diesel::{ExpressionMethods, insert_into, sql_query};
 use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use std::env;
+use std::sync::OnceLock;
+use tracing::info;
+
+static OSU_FILE_CACHE_SIZE: OnceLock<Option<i64>> = OnceLock::new();
+
+static OSU_FILE_CACHE_DAYS: OnceLock<Option<i64>> = OnceLock::new();
+
+const COMPRESSION_LEVEL: i32 = 9;
+// Files that were used more recently than this aren't touched again, so reads don't turn into
+// a write every time.
+const TOUCH_INTERVAL_HOURS: i64 = 24;
+const COMPRESSION_BATCH_SIZE: i64 = 100;
+
+/// Budget for the compressed files in bytes, unlimited unless `OSU_FILE_CACHE_SIZE` is set.
+fn cache_size() -> Option<i64> {
+    OSU_FILE_CACHE_SIZE
+        .get_or_init(|| {
+            env::var("OSU_FILE_CACHE_SIZE").ok().map(|size| {
+                size.parse::<i64>()
+                    .expect("Failed to parse osu! file cache size.")
+                    * 1024
+                    * 1024
+            })
+        })
+        .to_owned()
+}
+
+/// How long unused files are kept, forever unless `OSU_FILE_CACHE_DAYS` is set.
+fn cache_days() -> Option<i64> {
+    OSU_FILE_CACHE_DAYS
+        .get_or_init(|| {
+            env::var("OSU_FILE_CACHE_DAYS").ok().map(|days| {
+                days.parse::<i64>()
+                    .expect("Failed to parse osu! file cache days.")
+            })
+        })
+        .to_owned()
+}
 
 pub async fn create(
     db: &mut AsyncPgConnection,
     osu_files: Vec<(i64, Vec<u8>)>,
 ) -> Result<(), Error> {
     let mut items = Vec::new();
+    let mut ids = Vec::new();
 
     for osu_file in osu_files {
+        ids.push(osu_file.0);
         items.push(NewOsuFile {
             id: osu_file.0,
-            file: osu_file.1,
+            file: zstd::encode_all(osu_file.1.as_slice(), COMPRESSION_LEVEL)?,
+            compressed: true,
         });
     }
 
@@ -27,13 +71,111 @@ pub async fn create(
         .set((
             osu_files::id.eq(excluded(osu_files::id)),
             osu_files::file.eq(excluded(osu_files::file)),
+            osu_files::compressed.eq(excluded(osu_files::compressed)),
+            osu_files::time_cached.eq(now),
         ))
         .execute(db)
         .await?;
 
+    evict(db, &ids).await?;
+
     Ok(())
 }
 
+/// Turns a file read from the database back into the raw .osu file.
+pub fn decompress(mut osu_file: OsuFile) -> Result<OsuFile, Error> {
+    if osu_file.compressed {
+        osu_file.file = zstd::decode_all(osu_file.file.as_slice())?;
+        osu_file.compressed = false;
+    }
+    Ok(osu_file)
+}
+
+/// Marks files as used, which keeps them from being evicted.
+pub async fn touch(db: &mut AsyncPgConnection, ids: &[i64]) -> Result<(), Error> {
+    diesel::update(
+        osu_files::table
+            .filter(osu_files::id.eq_any(ids))
+            .filter(osu_files::time_cached.lt(Utc::now() - Duration::hours(TOUCH_INTERVAL_HOURS))),
+    )
+    .set(osu_files::time_cached.eq(now))
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
+
+/// Removes the least recently used files until the cache fits in its size and age budget.
+/// Evicted files get downloaded again the next time their beatmap is needed. Files in `keep` are
+/// never evicted, so a set that's bigger than the whole budget doesn't delete itself right after
+/// being cached.
+pub async fn evict(db: &mut AsyncPgConnection, keep: &[i64]) -> Result<(), Error> {
+    if let Some(days) = cache_days() {
+        diesel::delete(
+            osu_files::table
+                .filter(osu_files::time_cached.lt(Utc::now() - Duration::days(days)))
+                .filter(osu_files::id.ne_all(keep)),
+        )
+        .execute(db)
+        .await?;
+    }
+
+    if let Some(size) = cache_size() {
+        sql_query(
+            "DELETE FROM osu_files WHERE id IN (
+                SELECT id FROM (
+                    SELECT id, SUM(octet_length(file)) OVER (ORDER BY time_cached DESC, id) AS total
+                    FROM osu_files
+                ) AS sizes WHERE total > $1 AND id <> ALL($2)
+            )",
+        )
+        .bind::<BigInt, _>(size)
+        .bind::<Array<BigInt>, _>(keep)
+        .execute(db)
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Compresses files that were cached before compression was added. Runs in batches, so the bot
+/// can keep reading files while it's going.
+pub async fn compress_existing(db: &mut AsyncPgConnection) -> Result<usize, Error> {
+    let mut compressed_count = 0;
+
+    loop {
+        let batch = osu_files::table
+            .filter(osu_files::compressed.eq(false))
+            .select((osu_files::id, osu_files::file))
+            .limit(COMPRESSION_BATCH_SIZE)
+            .load::<(i64, Vec<u8>)>(db)
+            .await?;
+
+        if batch.is_empty() {
+            break;
+        }
+
+        for (id, file) in batch {
+            // A file that was replaced in the meantime is already compressed.
+            compressed_count += diesel::update(
+                osu_files::table
+                    .find(id)
+                    .filter(osu_files::compressed.eq(false)),
+            )
+            .set((
+                osu_files::file.eq(zstd::encode_all(file.as_slice(), COMPRESSION_LEVEL)?),
+                osu_files::compressed.eq(true),
+            ))
+            .execute(db)
+            .await?;
+        }
+
+        info!("Compressed {} cached osu! files", compressed_count);
+    }
+
+    Ok(compressed_count)
+}
+
 pub async fn count_entries(db: &mut AsyncPgConnection) -> Result<i64, Error> {
     Ok(osu_files::table
         .select(count(osu_files::id))
@@ -41,6 +183,15 @@ pub async fn count_entries(db: &mut AsyncPgConnection) -> Result<i64, Error> {
         .await?)
 }
 
+/// Size of all cached files in bytes, as they're stored.
+pub async fn total_size(db: &mut AsyncPgConnection) -> Result<i64, Error> {
+    Ok(osu_files::table
+        .select(sql::<Nullable<BigInt>>("SUM(octet_length(file))"))
+        .get_result::<Option<i64>>(db)
+        .await?
+        .unwrap_or(0))
+}
+
 pub async fn delete(db: &mut AsyncPgConnection, param_id: i64) -> Result<(), Error> {
     diesel::delete(osu_files::table.find(param_id))
         .execute(db)
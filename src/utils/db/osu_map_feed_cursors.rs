@@ -0,0 +1,29 @@
+use crate::models::osu_map_feed_cursors::{NewOsuMapFeedCursor, OsuMapFeedCursor};
+use crate::schema::osu_map_feed_cursors;
+use diesel::insert_into;
+use diesel::prelude::{ExpressionMethods, OptionalExtension, QueryDsl, QueryResult};
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+
+pub async fn create(
+    db: &mut AsyncPgConnection,
+    item: &NewOsuMapFeedCursor,
+) -> QueryResult<OsuMapFeedCursor> {
+    insert_into(osu_map_feed_cursors::table)
+        .values(item)
+        .on_conflict(osu_map_feed_cursors::status)
+        .do_update()
+        .set(item)
+        .get_result::<OsuMapFeedCursor>(db)
+        .await
+}
+
+pub async fn read(
+    db: &mut AsyncPgConnection,
+    param_status: &str,
+) -> QueryResult<Option<OsuMapFeedCursor>> {
+    osu_map_feed_cursors::table
+        .filter(osu_map_feed_cursors::status.eq(param_status))
+        .first::<OsuMapFeedCursor>(db)
+        .await
+        .optional()
+}
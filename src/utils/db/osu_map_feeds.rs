@@ -0,0 +1,25 @@
+use crate::models::osu_map_feeds::{NewOsuMapFeed, OsuMapFeed};
+use crate::schema::osu_map_feeds;
+use diesel::insert_into;
+use diesel::prelude::{ExpressionMethods, QueryDsl, QueryResult};
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+
+pub async fn create(db: &mut AsyncPgConnection, item: &NewOsuMapFeed) -> QueryResult<OsuMapFeed> {
+    insert_into(osu_map_feeds::table)
+        .values(item)
+        .on_conflict(osu_map_feeds::guild_id)
+        .do_update()
+        .set(item)
+        .get_result::<OsuMapFeed>(db)
+        .await
+}
+
+pub async fn get_all(db: &mut AsyncPgConnection) -> QueryResult<Vec<OsuMapFeed>> {
+    osu_map_feeds::table.load::<OsuMapFeed>(db).await
+}
+
+pub async fn delete(db: &mut AsyncPgConnection, param_guild_id: i64) -> QueryResult<usize> {
+    diesel::delete(osu_map_feeds::table.filter(osu_map_feeds::guild_id.eq(param_guild_id)))
+        .execute(db)
+        .await
+}
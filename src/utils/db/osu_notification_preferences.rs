@@ -0,0 +1,70 @@
+use crate::models::osu_notification_preferences::{
+    NewOsuNotificationPreference, OsuNotificationPreference,
+};
+use crate::schema::osu_notification_preferences;
+use chrono::Utc;
+use diesel::insert_into;
+use diesel::prelude::{ExpressionMethods, OptionalExtension, QueryDsl, QueryResult};
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+
+impl OsuNotificationPreference {
+    pub fn is_paused(&self) -> bool {
+        self.paused_until
+            .is_some_and(|paused_until| paused_until > Utc::now())
+    }
+
+    pub fn is_muted(&self, guild_id: i64) -> bool {
+        self.muted_guilds.contains(&Some(guild_id))
+    }
+}
+
+impl From<OsuNotificationPreference> for NewOsuNotificationPreference {
+    fn from(preference: OsuNotificationPreference) -> Self {
+        NewOsuNotificationPreference {
+            id: preference.id,
+            muted_guilds: preference.muted_guilds,
+            paused_until: preference.paused_until,
+            direct_message: preference.direct_message,
+        }
+    }
+}
+
+pub async fn create(
+    db: &mut AsyncPgConnection,
+    item: &NewOsuNotificationPreference,
+) -> QueryResult<OsuNotificationPreference> {
+    insert_into(osu_notification_preferences::table)
+        .values(item)
+        .on_conflict(osu_notification_preferences::id)
+        .do_update()
+        .set(item)
+        .get_result::<OsuNotificationPreference>(db)
+        .await
+}
+
+pub async fn read(
+    db: &mut AsyncPgConnection,
+    param_id: i64,
+) -> QueryResult<Option<OsuNotificationPreference>> {
+    osu_notification_preferences::table
+        .filter(osu_notification_preferences::id.eq(param_id))
+        .first::<OsuNotificationPreference>(db)
+        .await
+        .optional()
+}
+
+/// Reads a user's preferences, falling back to the defaults if they haven't set any.
+pub async fn read_or_default(
+    db: &mut AsyncPgConnection,
+    param_id: i64,
+) -> QueryResult<NewOsuNotificationPreference> {
+    Ok(read(db, param_id).await?.map_or_else(
+        || NewOsuNotificationPreference {
+            id: param_id,
+            muted_guilds: Vec::new(),
+            paused_until: None,
+            direct_message: false,
+        },
+        NewOsuNotificationPreference::from,
+    ))
+}
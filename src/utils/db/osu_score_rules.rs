@@ -0,0 +1,81 @@
+use crate::models::osu_score_rules::{NewOsuScoreRule, OsuScoreRule};
+use crate::schema::osu_score_rules;
+use diesel::insert_into;
+use diesel::prelude::{ExpressionMethods, QueryDsl, QueryResult};
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use rosu_v2::prelude::GameMode;
+
+/// What a score notification is checked against before it's posted in a channel.
+pub struct ScoreRuleCheck {
+    pub mode: GameMode,
+    pub pp: f64,
+    /// Position in the user's top 100, if the score is a top play.
+    pub position: Option<usize>,
+    pub ranked: bool,
+}
+
+impl OsuScoreRule {
+    pub fn allows(&self, check: &ScoreRuleCheck) -> bool {
+        if let Some(top_n) = self.top_n
+            && check
+                .position
+                .is_none_or(|position| position > usize::try_from(top_n).unwrap_or(0))
+        {
+            return false;
+        }
+
+        if let Some(min_pp) = self.min_pp
+            && check.pp < min_pp
+        {
+            return false;
+        }
+
+        if let Some(modes) = &self.modes
+            && !modes
+                .iter()
+                .flatten()
+                .any(|mode| *mode == check.mode.to_string())
+        {
+            return false;
+        }
+
+        self.include_unranked || check.ranked
+    }
+}
+
+pub async fn create(
+    db: &mut AsyncPgConnection,
+    item: &NewOsuScoreRule,
+) -> QueryResult<OsuScoreRule> {
+    insert_into(osu_score_rules::table)
+        .values(item)
+        .on_conflict((osu_score_rules::guild_id, osu_score_rules::channel_id))
+        .do_update()
+        .set(item)
+        .get_result::<OsuScoreRule>(db)
+        .await
+}
+
+pub async fn get_guild(
+    db: &mut AsyncPgConnection,
+    param_guild_id: i64,
+) -> QueryResult<Vec<OsuScoreRule>> {
+    osu_score_rules::table
+        .filter(osu_score_rules::guild_id.eq(param_guild_id))
+        .load::<OsuScoreRule>(db)
+        .await
+}
+
+pub async fn delete(
+    db: &mut AsyncPgConnection,
+    param_guild_id: i64,
+    param_channel_id: i64,
+) -> QueryResult<usize> {
+    diesel::delete(
+        osu_score_rules::table
+            .filter(osu_score_rules::guild_id.eq(param_guild_id))
+            .filter(osu_score_rules::channel_id.eq(param_channel_id)),
+    )
+    .execute(db)
+    .await
+}
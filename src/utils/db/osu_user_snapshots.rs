@@ -0,0 +1,127 @@
+use crate::Error;
+use crate::models::osu_user_snapshots::{NewOsuUserSnapshot, OsuUserSnapshot};
+use crate::models::osu_users::OsuUser;
+use chrono::{DateTime, Duration, Utc};
+use diesel::insert_into;
+use diesel::prelude::{ExpressionMethods, QueryDsl, QueryResult};
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use foldhash::{HashSet, HashSetExt};
+use std::env;
+use std::sync::OnceLock;
+
+static SNAPSHOT_RETENTION_DAYS: OnceLock<i64> = OnceLock::new();
+
+// Snapshots newer than this are all kept, older ones are reduced to one per day.
+const FULL_RESOLUTION_DAYS: i64 = 7;
+// Snapshots older than this are reduced to one per week.
+const DAILY_RESOLUTION_DAYS: i64 = 90;
+
+impl From<&OsuUser> for NewOsuUserSnapshot {
+    fn from(user: &OsuUser) -> Self {
+        NewOsuUserSnapshot {
+            osu_id: user.id,
+            mode: user.mode.clone(),
+            pp: user.pp,
+            accuracy: user.accuracy,
+            global_rank: user.global_rank,
+            country_rank: user.country_rank,
+            playcount: user.playcount,
+            ranked_score: user.ranked_score,
+        }
+    }
+}
+
+pub async fn create(
+    db: &mut AsyncPgConnection,
+    item: &NewOsuUserSnapshot,
+) -> Result<OsuUserSnapshot, Error> {
+    use crate::schema::osu_user_snapshots::dsl::osu_user_snapshots;
+
+    let snapshot = insert_into(osu_user_snapshots)
+        .values(item)
+        .get_result::<OsuUserSnapshot>(db)
+        .await?;
+
+    downsample(db, item.osu_id, &item.mode).await?;
+
+    Ok(snapshot)
+}
+
+pub async fn downsample(
+    db: &mut AsyncPgConnection,
+    param_osu_id: i64,
+    param_mode: &str,
+) -> Result<(), Error> {
+    use crate::schema::osu_user_snapshots::dsl::{
+        id, mode, osu_id, osu_user_snapshots, time_taken,
+    };
+
+    let retention_days = SNAPSHOT_RETENTION_DAYS
+        .get_or_init(|| {
+            env::var("SNAPSHOT_RETENTION_DAYS")
+                .unwrap_or_else(|_| String::from("730"))
+                .parse::<i64>()
+                .expect("Failed to parse snapshot retention days.")
+        })
+        .to_owned();
+
+    let now = Utc::now();
+
+    diesel::delete(
+        osu_user_snapshots
+            .filter(osu_id.eq(param_osu_id))
+            .filter(mode.eq(param_mode))
+            .filter(time_taken.lt(now - Duration::days(retention_days))),
+    )
+    .execute(db)
+    .await?;
+
+    let old_snapshots = osu_user_snapshots
+        .filter(osu_id.eq(param_osu_id))
+        .filter(mode.eq(param_mode))
+        .filter(time_taken.lt(now - Duration::days(FULL_RESOLUTION_DAYS)))
+        .order(time_taken.desc())
+        .load::<OsuUserSnapshot>(db)
+        .await?;
+
+    let weekly_cutoff = now - Duration::days(DAILY_RESOLUTION_DAYS);
+    let mut kept_buckets = HashSet::new();
+    let mut stale_ids = Vec::new();
+    for snapshot in old_snapshots {
+        let timestamp = snapshot.time_taken.timestamp();
+        let bucket = if snapshot.time_taken < weekly_cutoff {
+            (true, timestamp.div_euclid(7 * 86_400))
+        } else {
+            (false, timestamp.div_euclid(86_400))
+        };
+
+        if !kept_buckets.insert(bucket) {
+            stale_ids.push(snapshot.id);
+        }
+    }
+
+    if !stale_ids.is_empty() {
+        diesel::delete(osu_user_snapshots.filter(id.eq_any(stale_ids)))
+            .execute(db)
+            .await?;
+    }
+
+    Ok(())
+}
+
+pub async fn get_since(
+    db: &mut AsyncPgConnection,
+    param_osu_id: i64,
+    param_mode: &str,
+    since: DateTime<Utc>,
+) -> QueryResult<Vec<OsuUserSnapshot>> {
+    use crate::schema::osu_user_snapshots::dsl::{mode, osu_id, osu_user_snapshots, time_taken};
+
+    osu_user_snapshots
+        .filter(osu_id.eq(param_osu_id))
+        .filter(mode.eq(param_mode))
+        .filter(time_taken.ge(since))
+        .order(time_taken.asc())
+        .load::<OsuUserSnapshot>(db)
+        .await
+}
@@ -29,16 +29,17 @@ impl TryFrom<UserExtended> for NewOsuUser {
             ranked_score: i64::try_from(statistic.ranked_score)?,
             time_cached: Utc::now(),
             min_pp: 0.0,
+            playcount: i32::try_from(statistic.playcount)?,
         })
     }
 }
 
 pub async fn create(db: &mut AsyncPgConnection, item: &NewOsuUser) -> Result<OsuUser, Error> {
-    use crate::schema::osu_users::dsl::{id, osu_users};
+    use crate::schema::osu_users::dsl::{id, mode, osu_users};
 
     let user = insert_into(osu_users)
         .values(item)
-        .on_conflict(id)
+        .on_conflict((id, mode))
         .do_update()
         .set(item)
         .get_result(db)
@@ -50,9 +51,10 @@ pub async fn create(db: &mut AsyncPgConnection, item: &NewOsuUser) -> Result<Osu
 pub async fn update_ticks(
     db: &mut AsyncPgConnection,
     profile_id: i64,
+    profile_mode: &str,
     ticks: i32,
 ) -> Result<(), Error> {
-    diesel::update(osu_users::table.find(profile_id))
+    diesel::update(osu_users::table.find((profile_id, profile_mode)))
         .set(osu_users::ticks.eq(ticks))
         .execute(db)
         .await?;
@@ -60,12 +62,21 @@ pub async fn update_ticks(
     Ok(())
 }
 
-pub async fn read(db: &mut AsyncPgConnection, param_id: i64) -> QueryResult<OsuUser> {
-    use crate::schema::osu_users::dsl::{id, osu_users};
+pub async fn read(
+    db: &mut AsyncPgConnection,
+    param_id: i64,
+    param_mode: &str,
+) -> QueryResult<OsuUser> {
+    use crate::schema::osu_users::dsl::{id, mode, osu_users};
 
-    osu_users.filter(id.eq(param_id)).first::<OsuUser>(db).await
+    osu_users
+        .filter(id.eq(param_id))
+        .filter(mode.eq(param_mode))
+        .first::<OsuUser>(db)
+        .await
 }
 
+/// Removes the cached data of every mode of a user.
 pub async fn delete(db: &mut AsyncPgConnection, param_id: i64) -> Result<(), Error> {
     use crate::schema::osu_users::dsl::{id, osu_users};
 
@@ -76,6 +87,24 @@ pub async fn delete(db: &mut AsyncPgConnection, param_id: i64) -> Result<(), Err
     Ok(())
 }
 
+pub async fn delete_mode(
+    db: &mut AsyncPgConnection,
+    param_id: i64,
+    param_mode: &str,
+) -> Result<(), Error> {
+    use crate::schema::osu_users::dsl::{id, mode, osu_users};
+
+    diesel::delete(
+        osu_users
+            .filter(id.eq(param_id))
+            .filter(mode.eq(param_mode)),
+    )
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
+
 pub async fn get_all(db: &mut AsyncPgConnection) -> Result<Vec<OsuUser>, Error> {
     use crate::schema::osu_users::dsl::osu_users;
 
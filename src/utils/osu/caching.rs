@@ -10,7 +10,7 @@ use itertools::Itertools;
 use rosu_v2::Osu;
 use rosu_v2::prelude::BeatmapsetExtended;
 use std::sync::Arc;
-use tracing::info;
+use tracing::{error, info};
 
 pub async fn cache_beatmapset(
     connection: &mut AsyncPgConnection,
@@ -63,23 +63,34 @@ pub async fn get_beatmap(
     let query_beatmap = beatmaps::get_single(connection, i64::from(id)).await;
     if let Ok(beatmap) = query_beatmap {
         if check_valid_result(&beatmap.0.status, beatmap.0.time_cached) {
+            if let Err(why) = osu_file::touch(connection, &[beatmap.2.id]).await {
+                error!("Failed to mark osu! files as used: {}", why);
+            }
             return Ok(beatmap);
         }
         let beatmapset = osu_client.beatmapset_from_map_id(id).await?;
-        let to_delete = Some(check_if_deleted(
-            beatmapsets::read(connection, i64::from(beatmapset.mapset_id))
-                .await?
-                .ok_or("Beatmapset couldn't be fetched from db in get_beatmaps")?,
-            &beatmapset,
-        ));
+        let to_delete = find_deleted(connection, &beatmapset).await?;
         cache_beatmapset(connection, beatmapset, to_delete).await?;
         return Ok(beatmaps::get_single(connection, i64::from(id)).await?);
     }
     let beatmapset = osu_client.beatmapset_from_map_id(id).await?;
-    cache_beatmapset(connection, beatmapset, None).await?;
+    let to_delete = find_deleted(connection, &beatmapset).await?;
+    cache_beatmapset(connection, beatmapset, to_delete).await?;
     Ok(beatmaps::get_single(connection, i64::from(id)).await?)
 }
 
+pub async fn get_beatmap_by_checksum(
+    connection: &mut AsyncPgConnection,
+    osu_client: Arc<Osu>,
+    checksum: &str,
+) -> Result<(Beatmap, Beatmapset, OsuFile), Error> {
+    if let Ok(beatmap) = beatmaps::get_single_by_checksum(connection, checksum).await {
+        return get_beatmap(connection, osu_client, u32::try_from(beatmap.0.id)?).await;
+    }
+    let beatmap = osu_client.beatmap().checksum(checksum).await?;
+    get_beatmap(connection, osu_client, beatmap.map_id).await
+}
+
 pub async fn get_beatmapset(
     connection: &mut AsyncPgConnection,
     osu_client: Arc<Osu>,
@@ -88,17 +99,26 @@ pub async fn get_beatmapset(
     let query_beatmapset = beatmapsets::read(connection, i64::from(id)).await?;
     if let Some(query_beatmapset) = query_beatmapset {
         if check_valid_result(&query_beatmapset.0.status, query_beatmapset.0.time_cached) {
+            let ids = query_beatmapset
+                .1
+                .iter()
+                .map(|(beatmap, _)| beatmap.id)
+                .collect::<Vec<i64>>();
+            if let Err(why) = osu_file::touch(connection, &ids).await {
+                error!("Failed to mark osu! files as used: {}", why);
+            }
             return Ok(query_beatmapset);
         }
         let beatmapset = osu_client.beatmapset(id).await?;
-        let to_delete = Some(check_if_deleted(query_beatmapset, &beatmapset));
+        let to_delete = find_deleted(connection, &beatmapset).await?;
         cache_beatmapset(connection, beatmapset, to_delete).await?;
         return Ok(beatmapsets::read(connection, i64::from(id))
             .await?
             .ok_or("Failed to fetch beatmap in get_beatmapset")?);
     }
     let beatmapset = osu_client.beatmapset(id).await?;
-    cache_beatmapset(connection, beatmapset, None).await?;
+    let to_delete = find_deleted(connection, &beatmapset).await?;
+    cache_beatmapset(connection, beatmapset, to_delete).await?;
     Ok(beatmapsets::read(connection, i64::from(id))
         .await?
         .ok_or("Failed to fetch beatmap in get_beatmapset")?)
@@ -113,7 +133,7 @@ pub async fn get_updated_beatmapset(
     if let Some(query_beatmapset) = query_beatmapset {
         info!("Getting updated beatmapset with an existing beatmap");
         let beatmapset = osu_client.beatmapset(id).await?;
-        let to_delete = Some(check_if_deleted(query_beatmapset, &beatmapset));
+        let to_delete = find_deleted(connection, &beatmapset).await?;
         cache_beatmapset(connection, beatmapset, to_delete).await?;
         return Ok(beatmapsets::read(connection, i64::from(id))
             .await?
@@ -121,21 +141,36 @@ pub async fn get_updated_beatmapset(
     }
     info!("Getting updated beatmapset without an existing beatmap");
     let beatmapset = osu_client.beatmapset(id).await?;
-    cache_beatmapset(connection, beatmapset, None).await?;
+    let to_delete = find_deleted(connection, &beatmapset).await?;
+    cache_beatmapset(connection, beatmapset, to_delete).await?;
     Ok(beatmapsets::read(connection, i64::from(id))
         .await?
         .ok_or("Failed to fetch beatmap in get_beatmapset")?)
 }
 
+/// Looks for cached beatmaps that are no longer part of the set. This goes by the beatmap rows
+/// alone, since their .osu files may already have been evicted.
+async fn find_deleted(
+    connection: &mut AsyncPgConnection,
+    api_beatmapset: &BeatmapsetExtended,
+) -> Result<Option<Vec<i64>>, Error> {
+    let cached_beatmaps =
+        beatmaps::get_beatmapset(connection, i64::from(api_beatmapset.mapset_id)).await?;
+    if cached_beatmaps.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(check_if_deleted(&cached_beatmaps, api_beatmapset)))
+}
+
 pub fn check_if_deleted(
-    query_beatmapset: (Beatmapset, Vec<(Beatmap, OsuFile)>),
+    cached_beatmaps: &[Beatmap],
     api_beatmapset: &BeatmapsetExtended,
 ) -> Vec<i64> {
     let mut to_delete = Vec::new();
     if let Some(ref beatmaps) = api_beatmapset.maps {
-        for beatmap in query_beatmapset.1 {
-            if !beatmaps.iter().any(|x| x.map_id.eq(&(beatmap.0.id as u32))) {
-                to_delete.push(beatmap.0.id);
+        for beatmap in cached_beatmaps {
+            if !beatmaps.iter().any(|x| x.map_id.eq(&(beatmap.id as u32))) {
+                to_delete.push(beatmap.id);
             }
         }
     }
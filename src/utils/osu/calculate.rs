@@ -7,6 +7,8 @@ use crate::utils::osu::pp::mania::calculate_mania_pp;
 use crate::utils::osu::pp::osu::calculate_std_pp;
 use crate::utils::osu::pp::taiko::calculate_taiko_pp;
 use crate::utils::osu::pp::{CalculateResults, CatchScore, ManiaScore, StandardScore, TaikoScore};
+use crate::utils::osu::replay::Replay;
+use rosu_pp::GameMods;
 use rosu_v2::model::GameMode;
 
 pub fn calculate(
@@ -41,6 +43,7 @@ pub fn calculate(
                 ManiaScore {
                     mods: score.mods.clone().into(),
                     passed: score.passed,
+                    acc: None,
                     n320: Some(score.statistics.perfect),
                     n300: Some(score.statistics.great),
                     n200: Some(score.statistics.good),
@@ -69,6 +72,7 @@ pub fn calculate(
                     mods: score.mods.clone().into(),
                     passed: score.passed,
                     combo: Some(score.max_combo),
+                    acc: None,
                     fruits: Some(score.statistics.great),
                     droplets: Some(score.statistics.large_tick_hit),
                     tiny_droplets: Some(score.statistics.small_tick_hit),
@@ -89,3 +93,131 @@ pub fn calculate(
         GameMode::Catch => Ok(calculate_catch_pp(&osu_file.file, CatchScore::default())?),
     }
 }
+
+pub fn calculate_replay(replay: &Replay, osu_file: &OsuFile) -> Result<CalculateResults, Error> {
+    let mods = GameMods::from(replay.mods);
+
+    match replay.mode {
+        GameMode::Osu => Ok(calculate_std_pp(
+            &osu_file.file,
+            StandardScore {
+                mods,
+                combo: Some(replay.max_combo),
+                acc: Some(replay.accuracy()),
+                potential_acc: replay.potential_accuracy(),
+                n300: Some(replay.n300),
+                n100: Some(replay.n100),
+                n50: Some(replay.n50),
+                nmiss: Some(replay.nmiss),
+                ..Default::default()
+            },
+        )?),
+        GameMode::Mania => Ok(calculate_mania_pp(
+            &osu_file.file,
+            ManiaScore {
+                mods,
+                n320: Some(replay.ngeki),
+                n300: Some(replay.n300),
+                n200: Some(replay.nkatu),
+                n100: Some(replay.n100),
+                n50: Some(replay.n50),
+                nmiss: Some(replay.nmiss),
+                ..Default::default()
+            },
+        )?),
+        GameMode::Taiko => Ok(calculate_taiko_pp(
+            &osu_file.file,
+            TaikoScore {
+                mods,
+                combo: Some(replay.max_combo),
+                acc: Some(replay.accuracy()),
+                n300: Some(replay.n300),
+                n100: Some(replay.n100),
+                nmiss: Some(replay.nmiss),
+                ..Default::default()
+            },
+        )?),
+        GameMode::Catch => Ok(calculate_catch_pp(
+            &osu_file.file,
+            CatchScore {
+                mods,
+                combo: Some(replay.max_combo),
+                fruits: Some(replay.n300),
+                droplets: Some(replay.n100),
+                tiny_droplets: Some(replay.n50),
+                tiny_droplet_misses: Some(replay.nkatu),
+                nmiss: Some(replay.nmiss),
+                ..Default::default()
+            },
+        )?),
+    }
+}
+
+pub struct SimulatedScore {
+    pub mods: GameMods,
+    pub acc: Option<f64>,
+    pub n300: Option<u32>,
+    pub n100: Option<u32>,
+    pub n50: Option<u32>,
+    pub nmiss: Option<u32>,
+    pub combo: Option<u32>,
+}
+
+pub fn calculate_simulated(
+    osu_file: &OsuFile,
+    mode: GameMode,
+    simulated: &SimulatedScore,
+) -> Result<CalculateResults, Error> {
+    match mode {
+        GameMode::Osu => Ok(calculate_std_pp(
+            &osu_file.file,
+            StandardScore {
+                mods: simulated.mods.clone(),
+                combo: simulated.combo,
+                acc: simulated.acc,
+                n300: simulated.n300,
+                n100: simulated.n100,
+                n50: simulated.n50,
+                nmiss: simulated.nmiss,
+                ..Default::default()
+            },
+        )?),
+        GameMode::Mania => Ok(calculate_mania_pp(
+            &osu_file.file,
+            ManiaScore {
+                mods: simulated.mods.clone(),
+                acc: simulated.acc,
+                n300: simulated.n300,
+                n100: simulated.n100,
+                n50: simulated.n50,
+                nmiss: simulated.nmiss,
+                ..Default::default()
+            },
+        )?),
+        GameMode::Taiko => Ok(calculate_taiko_pp(
+            &osu_file.file,
+            TaikoScore {
+                mods: simulated.mods.clone(),
+                combo: simulated.combo,
+                acc: simulated.acc,
+                n300: simulated.n300,
+                n100: simulated.n100,
+                nmiss: simulated.nmiss,
+                ..Default::default()
+            },
+        )?),
+        GameMode::Catch => Ok(calculate_catch_pp(
+            &osu_file.file,
+            CatchScore {
+                mods: simulated.mods.clone(),
+                combo: simulated.combo,
+                acc: simulated.acc,
+                fruits: simulated.n300,
+                droplets: simulated.n100,
+                tiny_droplets: simulated.n50,
+                nmiss: simulated.nmiss,
+                ..Default::default()
+            },
+        )?),
+    }
+}
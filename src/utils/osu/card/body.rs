@@ -1,5 +1,6 @@
 use crate::Error;
 use crate::utils::misc::remove_trailing_zeros;
+use crate::utils::osu::card::CARD_HEIGHT;
 use crate::utils::osu::misc::get_score_rank;
 use aformat::aformat;
 use base64::Engine;
@@ -7,7 +8,8 @@ use base64::engine::general_purpose;
 use num_format::{Locale, ToFormattedString};
 use rosu_v2::prelude::{GradeCounts, UserExtended};
 use svg::Document;
-use svg::node::element::{Image, Mask, Rectangle, Text};
+use svg::node::element::path::Data;
+use svg::node::element::{Image, Line, Mask, Path, Rectangle, Text};
 
 pub async fn draw_body(mut document: Document, osu_user: &UserExtended) -> Result<Document, Error> {
     document = draw_ranks(document, osu_user).await?;
@@ -575,3 +577,92 @@ pub async fn draw_grades(document: Document, grades: &GradeCounts) -> Result<Doc
         .add(a_image)
         .add(a_text))
 }
+
+pub fn draw_rank_graph(document: Document, osu_user: &UserExtended) -> Document {
+    let ranks = osu_user
+        .rank_history
+        .as_deref()
+        .unwrap_or_default()
+        .iter()
+        .enumerate()
+        .filter(|(_, rank)| **rank > 0)
+        .map(|(day, rank)| (day as f64, f64::from(*rank)))
+        .collect::<Vec<(f64, f64)>>();
+
+    let top = f64::from(CARD_HEIGHT) + 12.0;
+    let bottom = f64::from(CARD_HEIGHT) + 65.0;
+    let left = 25.0;
+    let right = 350.0;
+
+    let rank_history_text = Text::new("Rank History")
+        .set("id", "rank_history_text")
+        .set("fill", "white")
+        .set("xml:space", "preserve")
+        .set("style", "white-space: pre")
+        .set("font-family", "Torus")
+        .set("font-size", 8)
+        .set("letter-spacing", "0em")
+        .set("x", left)
+        .set("y", top - 2.0);
+
+    let baseline = Line::new()
+        .set("x1", left)
+        .set("x2", right)
+        .set("y1", bottom)
+        .set("y2", bottom)
+        .set("stroke", "#46534F")
+        .set("stroke-width", 0.5);
+
+    let document = document.add(rank_history_text).add(baseline);
+
+    let (Some(first), Some(last)) = (ranks.first(), ranks.last()) else {
+        return document;
+    };
+
+    let best = ranks
+        .iter()
+        .map(|rank| rank.1)
+        .fold(f64::INFINITY, f64::min);
+    let worst = ranks
+        .iter()
+        .map(|rank| rank.1)
+        .fold(f64::NEG_INFINITY, f64::max);
+    let rank_range = (worst - best).max(1.0);
+    let day_range = (last.0 - first.0).max(1.0);
+
+    let mut data = Data::new();
+    for (index, (day, rank)) in ranks.iter().enumerate() {
+        let x = left + (day - first.0) / day_range * (right - left);
+        let y = top + 8.0 + (rank - best) / rank_range * (bottom - top - 12.0);
+        data = if index == 0 {
+            data.move_to((x, y))
+        } else {
+            data.line_to((x, y))
+        };
+    }
+
+    let rank_graph = Path::new()
+        .set("id", "rank_graph")
+        .set("d", data)
+        .set("fill", "none")
+        .set("stroke", "#FFCC22")
+        .set("stroke-width", 1.5)
+        .set("stroke-linejoin", "round");
+
+    let best_rank_text = Text::new(format!(
+        "Peak #{}",
+        (best as u32).to_formatted_string(&Locale::en)
+    ))
+    .set("id", "best_rank_text")
+    .set("fill", "#DBF0E9")
+    .set("xml:space", "preserve")
+    .set("style", "white-space: pre")
+    .set("font-family", "Torus")
+    .set("font-size", 8)
+    .set("letter-spacing", "0em")
+    .set("x", right)
+    .set("y", top - 2.0)
+    .set("text-anchor", "end");
+
+    document.add(rank_graph).add(best_rank_text)
+}
@@ -20,8 +20,9 @@ pub async fn draw_header(
     mut document: Document,
     osu_user: &UserExtended,
     color: Colour,
+    height: u32,
 ) -> Result<Document, Error> {
-    document = draw_avatar_and_cover(document, osu_user, color).await?;
+    document = draw_avatar_and_cover(document, osu_user, color, height).await?;
     document = draw_following_pill(document, osu_user);
     document = draw_osu_circle(document);
     document = draw_username(document, osu_user.username.as_str());
@@ -51,6 +52,7 @@ pub async fn draw_avatar_and_cover(
     document: Document,
     osu_user: &UserExtended,
     color: Colour,
+    height: u32,
 ) -> Result<Document, Error> {
     let header_rect = Rectangle::new()
         .set("x", 0)
@@ -105,7 +107,7 @@ pub async fn draw_avatar_and_cover(
         .set("x", 0)
         .set("y", 0)
         .set("width", 375)
-        .set("height", 235)
+        .set("height", height)
         .set("rx", 10)
         .set("ry", 10)
         .set("fill", "#2E3835");
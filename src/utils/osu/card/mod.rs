@@ -12,9 +12,24 @@ use rosu_v2::prelude::UserExtended;
 use std::sync::Arc;
 use svg::Document;
 
-pub async fn render_card(osu_user: &UserExtended, color: Colour) -> Result<Pixmap, Error> {
-    let svg = load_svg(osu_user, color).await?;
-    let mut pixmap = Pixmap::new(1500, 940).unwrap();
+pub const CARD_HEIGHT: u32 = 235;
+pub const RANK_GRAPH_HEIGHT: u32 = 75;
+
+pub fn card_height(with_rank_graph: bool) -> u32 {
+    if with_rank_graph {
+        CARD_HEIGHT + RANK_GRAPH_HEIGHT
+    } else {
+        CARD_HEIGHT
+    }
+}
+
+pub async fn render_card(
+    osu_user: &UserExtended,
+    color: Colour,
+    with_rank_graph: bool,
+) -> Result<Pixmap, Error> {
+    let svg = load_svg(osu_user, color, with_rank_graph).await?;
+    let mut pixmap = Pixmap::new(1500, card_height(with_rank_graph) * 4).unwrap();
     render(&svg, Transform::default(), &mut pixmap.as_mut());
     Ok(pixmap)
 }
@@ -25,9 +40,13 @@ pub fn load_fonts() -> Database {
     database
 }
 
-pub async fn load_svg(osu_user: &UserExtended, color: Colour) -> Result<Tree, Error> {
+pub async fn load_svg(
+    osu_user: &UserExtended,
+    color: Colour,
+    with_rank_graph: bool,
+) -> Result<Tree, Error> {
     Ok(Tree::from_str(
-        &generate_svg(osu_user, color).await?,
+        &generate_svg(osu_user, color, with_rank_graph).await?,
         &usvg::Options {
             fontdb: Arc::from(load_fonts()),
             ..Default::default()
@@ -51,16 +70,24 @@ fn adjust_saturation_and_brightness(color: Colour, saturation: f64, brightness:
     )
 }
 
-pub async fn generate_svg(osu_user: &UserExtended, color: Colour) -> Result<String, Error> {
+pub async fn generate_svg(
+    osu_user: &UserExtended,
+    color: Colour,
+    with_rank_graph: bool,
+) -> Result<String, Error> {
     let color = adjust_saturation_and_brightness(color, 0.45, 0.3);
+    let height = card_height(with_rank_graph);
 
     let mut document = Document::new()
-        .set("viewBox", (0, 0, 375, 235))
+        .set("viewBox", (0, 0, 375, height))
         .set("xmlns:xlink", "http://www.w3.org/1999/xlink")
         .set("fill", "none")
         .set("width", 1500)
-        .set("height", 940);
-    document = header::draw_header(document, osu_user, color).await?;
+        .set("height", height * 4);
+    document = header::draw_header(document, osu_user, color, height).await?;
     document = body::draw_body(document, osu_user).await?;
+    if with_rank_graph {
+        document = body::draw_rank_graph(document, osu_user);
+    }
     Ok(document.to_string())
 }
@@ -150,17 +150,23 @@ pub async fn send_scores_embed(
         false
     };
 
-    let formatted_scores = format_score_list(&best_scores, None, None, minimal_formatting)?;
+    let mut pages = Vec::new();
+    for page in 0..count_score_pages(best_scores.len(), 5) {
+        pages.push(format_score_list(
+            &best_scores,
+            None,
+            Some(page * 5),
+            minimal_formatting,
+        )?);
+    }
 
     let user_link = format_user_link(i64::from(user.user_id));
 
-    let footer = format!("Page {} of {}", 1, count_score_pages(best_scores.len(), 5));
-
     let embed = create_embed(
         color,
         thumbnail,
-        &formatted_scores,
-        &footer,
+        "",
+        "",
         &user.avatar_url,
         &user.username,
         &user_link,
@@ -168,7 +174,47 @@ pub async fn send_scores_embed(
         None,
     );
 
-    if best_scores.len() > 5 {
+    send_paginated_embed(ctx, embed, pages).await
+}
+
+pub async fn send_list_embed(
+    ctx: Context<'_>,
+    entries: Vec<String>,
+    entries_per_page: usize,
+    title: String,
+    thumbnail: Option<String>,
+) -> Result<(), Error> {
+    let color = match ctx.author_member().await {
+        None => BLUE,
+        Some(member) => member.colour(ctx.cache()).unwrap_or(BLUE),
+    };
+
+    let pages = entries
+        .chunks(entries_per_page)
+        .map(|page| page.join("\n"))
+        .collect::<Vec<String>>();
+
+    let mut embed = CreateEmbed::new().color(color).title(title);
+
+    if let Some(thumbnail) = thumbnail {
+        embed = embed.thumbnail(thumbnail, None);
+    }
+
+    send_paginated_embed(ctx, embed, pages).await
+}
+
+/// Sends `embed` with the first of `pages` as its description, and buttons to flip through the
+/// rest if there's more than one.
+async fn send_paginated_embed(
+    ctx: Context<'_>,
+    embed: CreateEmbed<'_>,
+    pages: Vec<String>,
+) -> Result<(), Error> {
+    let max_pages = pages.len().max(1);
+
+    let first_page = page_embed(&embed, &pages, 1, max_pages);
+
+    if max_pages > 1 {
         let buttons = [
             CreateButton::new("last_page").label("<"),
             CreateButton::new("next_page").label(">"),
@@ -179,22 +225,17 @@ pub async fn send_scores_embed(
             &buttons,
         ))];
 
-        let builder = CreateReply::default().embed(embed).components(&components);
+        let builder = CreateReply::default()
+            .embed(first_page)
+            .components(&components);
 
         let reply = ctx.send(builder).await?;
 
-        TopScorePaginator::new(
-            ctx,
-            reply,
-            best_scores,
-            color,
-            user.clone(),
-            minimal_formatting,
-        )
-        .handle_interactions()
-        .await?;
+        Paginator::new(ctx, reply, embed, pages)
+            .handle_interactions()
+            .await?;
     } else {
-        let builder = CreateReply::default().embed(embed);
+        let builder = CreateReply::default().embed(first_page);
 
         ctx.send(builder).await?;
     }
@@ -202,38 +243,44 @@ pub async fn send_scores_embed(
     Ok(())
 }
 
-struct TopScorePaginator<'a> {
+fn page_embed<'a>(
+    embed: &CreateEmbed<'a>,
+    pages: &[String],
+    page: usize,
+    max_pages: usize,
+) -> CreateEmbed<'a> {
+    embed
+        .clone()
+        .description(pages.get(page - 1).cloned().unwrap_or_default())
+        .footer(CreateEmbedFooter::new(format!(
+            "Page {page} of {max_pages}"
+        )))
+}
+
+struct Paginator<'a, 'b> {
     ctx: Context<'a>,
     reply: ReplyHandle<'a>,
-    best_scores: Vec<(Score, usize, Beatmap, Beatmapset, CalculateResults)>,
-    color: Colour,
-    user: UserExtended,
+    embed: CreateEmbed<'b>,
+    pages: Vec<String>,
     page: usize,
-    offset: usize,
     max_pages: usize,
-    minimal_formatting: bool,
 }
 
-impl TopScorePaginator<'_> {
-    fn new<'a>(
+impl<'a, 'b> Paginator<'a, 'b> {
+    fn new(
         ctx: Context<'a>,
         reply: ReplyHandle<'a>,
-        best_scores: Vec<(Score, usize, Beatmap, Beatmapset, CalculateResults)>,
-        color: Colour,
-        user: UserExtended,
-        mimimal_formatting: bool,
-    ) -> TopScorePaginator<'a> {
-        let max_pages = count_score_pages(best_scores.len(), 5);
-        TopScorePaginator {
+        embed: CreateEmbed<'b>,
+        pages: Vec<String>,
+    ) -> Paginator<'a, 'b> {
+        let max_pages = pages.len().max(1);
+        Paginator {
             ctx,
             reply,
-            best_scores,
-            color,
-            user,
+            embed,
+            pages,
             page: 1,
-            offset: 0,
             max_pages,
-            minimal_formatting: mimimal_formatting,
         }
     }
 
@@ -252,26 +299,21 @@ impl TopScorePaginator<'_> {
                 "last_page" => {
                     if self.page == 1 {
                         self.page = self.max_pages;
-                        self.offset = (self.max_pages - 1) * 5;
                     } else {
                         self.page -= 1;
-                        self.offset -= 5;
                     }
                     self.update_page(&interaction).await?;
                 }
                 "next_page" => {
                     if self.page == self.max_pages {
                         self.page = 1;
-                        self.offset = 0;
                     } else {
                         self.page += 1;
-                        self.offset += 5;
                     }
                     self.update_page(&interaction).await?;
                 }
                 "reset" => {
                     self.page = 1;
-                    self.offset = 0;
                     self.update_page(&interaction).await?;
                 }
                 _ => {}
@@ -282,28 +324,7 @@ impl TopScorePaginator<'_> {
     }
 
     async fn update_page(&self, interaction: &ComponentInteraction) -> Result<(), Error> {
-        let formatted_scores = format_score_list(
-            &self.best_scores,
-            None,
-            Some(self.offset),
-            self.minimal_formatting,
-        )?;
-
-        let footer = format!("Page {} of {}", self.page, self.max_pages);
-
-        let user_link = format_user_link(i64::from(self.user.user_id));
-
-        let embed = create_embed(
-            self.color,
-            &self.user.avatar_url,
-            &formatted_scores,
-            &footer,
-            &self.user.avatar_url,
-            &self.user.username,
-            &user_link,
-            None,
-            None,
-        );
+        let embed = page_embed(&self.embed, &self.pages, self.page, self.max_pages);
 
         let interaction_response = CreateInteractionResponseMessage::new().embed(embed);
 
@@ -315,28 +336,7 @@ impl TopScorePaginator<'_> {
     }
 
     async fn stop_paginator(&self) -> Result<(), Error> {
-        let formatted_scores = format_score_list(
-            &self.best_scores,
-            None,
-            Some(self.offset),
-            self.minimal_formatting,
-        )?;
-
-        let footer = format!("Page {} of {}", self.page, self.max_pages);
-
-        let user_link = format_user_link(i64::from(self.user.user_id));
-
-        let embed = create_embed(
-            self.color,
-            &self.user.avatar_url,
-            &formatted_scores,
-            &footer,
-            &self.user.avatar_url,
-            &self.user.username,
-            &user_link,
-            None,
-            None,
-        );
+        let embed = page_embed(&self.embed, &self.pages, self.page, self.max_pages);
 
         let builder = CreateReply::default().embed(embed).components(vec![]);
 
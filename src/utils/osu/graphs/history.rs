@@ -0,0 +1,134 @@
+use crate::Error;
+use crate::utils::osu::graphs::{
+    BACKGROUND_COLOR, ChartArea, draw_line_chart, label, new_document, render_document,
+};
+use chrono::DateTime;
+use num_format::{Locale, ToFormattedString};
+use resvg::tiny_skia::Pixmap;
+use svg::node::element::Rectangle;
+
+const WIDTH: u32 = 1000;
+const CHART_HEIGHT: u32 = 240;
+const HEADER_HEIGHT: u32 = 80;
+const MARGIN: f64 = 30.0;
+
+const PP_COLOR: &str = "#FF66AB";
+const RANK_COLOR: &str = "#FFCC22";
+const ACCURACY_COLOR: &str = "#66CCFF";
+
+/// Points are (unix timestamp, value) pairs in chronological order.
+#[derive(Default)]
+pub struct HistorySeries {
+    pub pp: Vec<(f64, f64)>,
+    pub global_rank: Vec<(f64, f64)>,
+    pub accuracy: Vec<(f64, f64)>,
+}
+
+impl HistorySeries {
+    fn timespan(&self) -> Option<(f64, f64)> {
+        [&self.pp, &self.global_rank, &self.accuracy]
+            .iter()
+            .filter_map(|series| Some((series.first()?.0, series.last()?.0)))
+            .reduce(|a, b| (a.0.min(b.0), a.1.max(b.1)))
+    }
+}
+
+struct Chart<'a> {
+    title: &'static str,
+    points: &'a [(f64, f64)],
+    invert: bool,
+    color: &'static str,
+    format_value: fn(f64) -> String,
+}
+
+fn format_date(timestamp: f64) -> String {
+    DateTime::from_timestamp(timestamp as i64, 0)
+        .map(|date| date.format("%Y-%m-%d").to_string())
+        .unwrap_or_default()
+}
+
+pub fn render_history(title: &str, series: &HistorySeries) -> Result<Pixmap, Error> {
+    let charts = [
+        Chart {
+            title: "Performance",
+            points: &series.pp,
+            invert: false,
+            color: PP_COLOR,
+            format_value: |value| {
+                format!(
+                    "{}pp",
+                    (value.round() as u64).to_formatted_string(&Locale::en)
+                )
+            },
+        },
+        Chart {
+            title: "Global Rank",
+            points: &series.global_rank,
+            invert: true,
+            color: RANK_COLOR,
+            format_value: |value| {
+                format!(
+                    "#{}",
+                    (value.round() as u64).to_formatted_string(&Locale::en)
+                )
+            },
+        },
+        Chart {
+            title: "Accuracy",
+            points: &series.accuracy,
+            invert: false,
+            color: ACCURACY_COLOR,
+            format_value: |value| format!("{value:.2}%"),
+        },
+    ]
+    .into_iter()
+    .filter(|chart| chart.points.len() > 1)
+    .collect::<Vec<Chart>>();
+
+    if charts.is_empty() {
+        return Err(Error::from("Not enough history to draw a graph"));
+    }
+
+    let height = HEADER_HEIGHT + CHART_HEIGHT * u32::try_from(charts.len())?;
+
+    let mut document = new_document(WIDTH, height)
+        .add(
+            Rectangle::new()
+                .set("width", WIDTH)
+                .set("height", height)
+                .set("fill", BACKGROUND_COLOR),
+        )
+        .add(label(title, MARGIN, 45.0, 28));
+
+    if let Some((start, end)) = series.timespan() {
+        document = document.add(
+            label(
+                format!("{} – {}", format_date(start), format_date(end)),
+                f64::from(WIDTH) - MARGIN,
+                45.0,
+                16,
+            )
+            .set("text-anchor", "end"),
+        );
+    }
+
+    for (index, chart) in charts.into_iter().enumerate() {
+        let area = ChartArea {
+            x: MARGIN,
+            y: f64::from(HEADER_HEIGHT) + (index as f64) * f64::from(CHART_HEIGHT),
+            width: f64::from(WIDTH) - MARGIN * 2.0,
+            height: f64::from(CHART_HEIGHT) - 20.0,
+        };
+        document = draw_line_chart(
+            document,
+            &area,
+            chart.title,
+            chart.points,
+            chart.invert,
+            chart.color,
+            chart.format_value,
+        );
+    }
+
+    render_document(&document, WIDTH, height)
+}
@@ -0,0 +1,152 @@
+pub mod history;
+pub mod playfield;
+pub mod replay;
+pub mod strains;
+
+use crate::Error;
+use crate::utils::osu::card::load_fonts;
+use resvg::tiny_skia::Pixmap;
+use resvg::usvg::{Transform, Tree};
+use resvg::{render, usvg};
+use std::sync::Arc;
+use svg::Document;
+use svg::node::element::path::Data;
+use svg::node::element::{Line, Path, Rectangle, Text};
+
+pub const BACKGROUND_COLOR: &str = "#2A2226";
+pub const PANEL_COLOR: &str = "#382E32";
+pub const GRID_COLOR: &str = "#5C4D53";
+
+pub fn new_document(width: u32, height: u32) -> Document {
+    Document::new()
+        .set("viewBox", (0, 0, width, height))
+        .set("xmlns:xlink", "http://www.w3.org/1999/xlink")
+        .set("fill", "none")
+        .set("width", width)
+        .set("height", height)
+}
+
+pub fn render_document(document: &Document, width: u32, height: u32) -> Result<Pixmap, Error> {
+    let tree = Tree::from_str(
+        &document.to_string(),
+        &usvg::Options {
+            fontdb: Arc::from(load_fonts()),
+            ..Default::default()
+        },
+    )?;
+    let mut pixmap = Pixmap::new(width, height).ok_or("Failed to create pixmap")?;
+    render(&tree, Transform::default(), &mut pixmap.as_mut());
+    Ok(pixmap)
+}
+
+pub fn label(text: impl Into<String>, x: f64, y: f64, font_size: u32) -> Text {
+    Text::new(text)
+        .set("fill", "white")
+        .set("xml:space", "preserve")
+        .set("style", "white-space: pre")
+        .set("font-family", "Torus")
+        .set("font-size", font_size)
+        .set("letter-spacing", "0em")
+        .set("x", x)
+        .set("y", y)
+}
+
+pub struct ChartArea {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+/// Draws a labelled line chart of `points` (x, y) into `area`. When `invert` is set, lower
+/// values are drawn higher up, which is what you want for ranks.
+pub fn draw_line_chart(
+    mut document: Document,
+    area: &ChartArea,
+    title: &str,
+    points: &[(f64, f64)],
+    invert: bool,
+    color: &str,
+    format_value: impl Fn(f64) -> String,
+) -> Document {
+    document = document
+        .add(
+            Rectangle::new()
+                .set("x", area.x)
+                .set("y", area.y)
+                .set("width", area.width)
+                .set("height", area.height)
+                .set("rx", 6)
+                .set("fill", PANEL_COLOR),
+        )
+        .add(label(title, area.x + 12.0, area.y + 26.0, 18));
+
+    let (Some(first), Some(last)) = (points.first(), points.last()) else {
+        return document;
+    };
+
+    let min_y = points
+        .iter()
+        .map(|point| point.1)
+        .fold(f64::INFINITY, f64::min);
+    let max_y = points
+        .iter()
+        .map(|point| point.1)
+        .fold(f64::NEG_INFINITY, f64::max);
+    let x_range = (last.0 - first.0).max(1.0);
+    let y_range = if max_y - min_y > f64::EPSILON {
+        max_y - min_y
+    } else {
+        1.0
+    };
+
+    let left = area.x + 90.0;
+    let right = area.x + area.width - 20.0;
+    let top = area.y + 45.0;
+    let bottom = area.y + area.height - 20.0;
+
+    for step in 0..3 {
+        let y = top + (bottom - top) * f64::from(step) / 2.0;
+        let value = if invert {
+            min_y + y_range * f64::from(step) / 2.0
+        } else {
+            max_y - y_range * f64::from(step) / 2.0
+        };
+        document = document
+            .add(
+                Line::new()
+                    .set("x1", left)
+                    .set("x2", right)
+                    .set("y1", y)
+                    .set("y2", y)
+                    .set("stroke", GRID_COLOR)
+                    .set("stroke-width", 1),
+            )
+            .add(label(format_value(value), area.x + 12.0, y + 5.0, 13));
+    }
+
+    let mut data = Data::new();
+    for (index, (x, y)) in points.iter().enumerate() {
+        let position_x = left + (x - first.0) / x_range * (right - left);
+        let normalized = (y - min_y) / y_range;
+        let position_y = if invert {
+            top + normalized * (bottom - top)
+        } else {
+            bottom - normalized * (bottom - top)
+        };
+        data = if index == 0 {
+            data.move_to((position_x, position_y))
+        } else {
+            data.line_to((position_x, position_y))
+        };
+    }
+
+    document.add(
+        Path::new()
+            .set("d", data)
+            .set("fill", "none")
+            .set("stroke", color)
+            .set("stroke-width", 3)
+            .set("stroke-linejoin", "round"),
+    )
+}
@@ -0,0 +1,282 @@
+use crate::Error;
+use crate::utils::osu::graphs::{
+    BACKGROUND_COLOR, GRID_COLOR, PANEL_COLOR, label, new_document, render_document,
+};
+use crate::utils::osu::playfield::{ObjectKind, Playfield, PlayfieldObject};
+use resvg::tiny_skia::Pixmap;
+use svg::Document;
+use svg::node::element::path::Data;
+use svg::node::element::{Circle, Group, Path, Rectangle};
+
+const WIDTH: u32 = 900;
+const HEIGHT: u32 = 758;
+
+const MARGIN: f64 = 30.0;
+const PANEL_TOP: f64 = 80.0;
+// Room around the playfield, since objects near the edges stick out of it.
+const PANEL_PADDING: f64 = 36.0;
+
+const PLAYFIELD_WIDTH: f64 = 512.0;
+const PLAYFIELD_HEIGHT: f64 = 384.0;
+const SCALE: f64 = 1.5;
+
+const FOLLOW_POINT_SPACING: f64 = 32.0;
+
+struct Origin {
+    x: f64,
+    y: f64,
+}
+
+impl Origin {
+    fn to_pixels(&self, (x, y): (f64, f64)) -> (f64, f64) {
+        (self.x + x * SCALE, self.y + y * SCALE)
+    }
+}
+
+fn rgb((r, g, b): (u8, u8, u8)) -> String {
+    format!("rgb({r},{g},{b})")
+}
+
+fn darken((r, g, b): (u8, u8, u8)) -> String {
+    let darken = |channel: u8| (f64::from(channel) * 0.35).round();
+    format!("rgb({},{},{})", darken(r), darken(g), darken(b))
+}
+
+/// Draws every object of `playfield` that's on screen at `time`, the way the osu! editor would.
+pub fn render_playfield(title: &str, playfield: &Playfield, time: f64) -> Result<Pixmap, Error> {
+    let origin = Origin {
+        x: (f64::from(WIDTH) - PLAYFIELD_WIDTH * SCALE) / 2.0,
+        y: PANEL_TOP + PANEL_PADDING,
+    };
+
+    let mut document = new_document(WIDTH, HEIGHT)
+        .add(
+            Rectangle::new()
+                .set("width", WIDTH)
+                .set("height", HEIGHT)
+                .set("fill", BACKGROUND_COLOR),
+        )
+        .add(
+            Rectangle::new()
+                .set("x", MARGIN)
+                .set("y", PANEL_TOP)
+                .set("width", f64::from(WIDTH) - MARGIN * 2.0)
+                .set("height", PLAYFIELD_HEIGHT * SCALE + PANEL_PADDING * 2.0)
+                .set("rx", 6)
+                .set("fill", PANEL_COLOR),
+        )
+        .add(
+            Rectangle::new()
+                .set("x", origin.x)
+                .set("y", origin.y)
+                .set("width", PLAYFIELD_WIDTH * SCALE)
+                .set("height", PLAYFIELD_HEIGHT * SCALE)
+                .set("stroke", GRID_COLOR)
+                .set("stroke-width", 1)
+                .set("stroke-dasharray", "6 6"),
+        )
+        .add(label(title, MARGIN, 50.0, 24));
+
+    let visible = playfield
+        .objects
+        .iter()
+        .enumerate()
+        .filter(|(_, object)| playfield.is_visible(object, time))
+        .collect::<Vec<(usize, &PlayfieldObject)>>();
+
+    if visible.is_empty() {
+        document = document.add(
+            label(
+                "Nothing on screen",
+                f64::from(WIDTH) / 2.0,
+                origin.y + PLAYFIELD_HEIGHT * SCALE / 2.0,
+                24,
+            )
+            .set("text-anchor", "middle")
+            .set("fill", GRID_COLOR),
+        );
+        return render_document(&document, WIDTH, HEIGHT);
+    }
+
+    // Follow points go underneath everything else.
+    for (index, object) in &visible {
+        let Some(previous) = index
+            .checked_sub(1)
+            .and_then(|index| playfield.objects.get(index))
+        else {
+            continue;
+        };
+        if object.new_combo || time >= object.start_time {
+            continue;
+        }
+        document = draw_follow_points(document, &origin, playfield, previous, object);
+    }
+
+    // Earlier objects are drawn on top of later ones.
+    for (_, object) in visible.iter().rev() {
+        let opacity = ((time - (object.start_time - playfield.preempt())) / playfield.fade_in())
+            .clamp(0.0, 1.0);
+        document =
+            document.add(draw_object(&origin, playfield, object, time).set("opacity", opacity));
+    }
+
+    render_document(&document, WIDTH, HEIGHT)
+}
+
+fn draw_follow_points(
+    mut document: Document,
+    origin: &Origin,
+    playfield: &Playfield,
+    previous: &PlayfieldObject,
+    object: &PlayfieldObject,
+) -> Document {
+    let (start_x, start_y) = previous.end_position();
+    let distance = (object.x - start_x).hypot(object.y - start_y);
+    let radius = playfield.radius();
+
+    let mut travelled = radius + FOLLOW_POINT_SPACING / 2.0;
+    while travelled < distance - radius {
+        let t = travelled / distance;
+        let (x, y) = origin.to_pixels((
+            start_x + (object.x - start_x) * t,
+            start_y + (object.y - start_y) * t,
+        ));
+        document = document.add(
+            Circle::new()
+                .set("cx", x)
+                .set("cy", y)
+                .set("r", 2.5)
+                .set("fill", "white")
+                .set("fill-opacity", 0.6),
+        );
+        travelled += FOLLOW_POINT_SPACING;
+    }
+
+    document
+}
+
+fn draw_object(
+    origin: &Origin,
+    playfield: &Playfield,
+    object: &PlayfieldObject,
+    time: f64,
+) -> Group {
+    let radius = playfield.radius() * SCALE;
+    let (x, y) = origin.to_pixels((object.x, object.y));
+    let mut group = Group::new();
+
+    match &object.kind {
+        ObjectKind::Spinner => {
+            let (center_x, center_y) =
+                origin.to_pixels((PLAYFIELD_WIDTH / 2.0, PLAYFIELD_HEIGHT / 2.0));
+            return group
+                .add(
+                    Circle::new()
+                        .set("cx", center_x)
+                        .set("cy", center_y)
+                        .set("r", PLAYFIELD_HEIGHT * SCALE * 0.45)
+                        .set("stroke", "white")
+                        .set("stroke-width", 4),
+                )
+                .add(
+                    Circle::new()
+                        .set("cx", center_x)
+                        .set("cy", center_y)
+                        .set("r", 6)
+                        .set("fill", "white"),
+                );
+        }
+        ObjectKind::Slider { path, repeats } => {
+            let mut data = Data::new();
+            for (index, point) in path.iter().enumerate() {
+                let point = origin.to_pixels(*point);
+                data = if index == 0 {
+                    data.move_to(point)
+                } else {
+                    data.line_to(point)
+                };
+            }
+
+            group = group
+                .add(
+                    Path::new()
+                        .set("d", data.clone())
+                        .set("stroke", "white")
+                        .set("stroke-width", radius * 2.0)
+                        .set("stroke-linecap", "round")
+                        .set("stroke-linejoin", "round"),
+                )
+                .add(
+                    Path::new()
+                        .set("d", data)
+                        .set("stroke", darken(object.combo_color))
+                        .set("stroke-width", radius * 1.75)
+                        .set("stroke-linecap", "round")
+                        .set("stroke-linejoin", "round"),
+                );
+
+            if *repeats > 1
+                && let Some(end) = path.last()
+            {
+                let (end_x, end_y) = origin.to_pixels(*end);
+                group = group.add(
+                    label(
+                        format!("×{repeats}"),
+                        end_x,
+                        end_y + radius * 0.3,
+                        (radius * 0.8) as u32,
+                    )
+                    .set("text-anchor", "middle"),
+                );
+            }
+
+            if time >= object.start_time {
+                let (ball_x, ball_y) = origin.to_pixels(object.position_at(time));
+                return group.add(
+                    Circle::new()
+                        .set("cx", ball_x)
+                        .set("cy", ball_y)
+                        .set("r", radius * 0.85)
+                        .set("fill", rgb(object.combo_color))
+                        .set("stroke", "white")
+                        .set("stroke-width", radius * 0.12),
+                );
+            }
+        }
+        ObjectKind::Circle => {}
+    }
+
+    group = group
+        .add(
+            Circle::new()
+                .set("cx", x)
+                .set("cy", y)
+                .set("r", radius * 0.93)
+                .set("fill", rgb(object.combo_color))
+                .set("stroke", "white")
+                .set("stroke-width", radius * 0.14),
+        )
+        .add(
+            label(
+                object.combo_number.to_string(),
+                x,
+                y + radius * 0.35,
+                (radius * 0.95) as u32,
+            )
+            .set("text-anchor", "middle"),
+        );
+
+    if time < object.start_time {
+        let approach_scale = 1.0 + 3.0 * (object.start_time - time) / playfield.preempt();
+        group = group.add(
+            Circle::new()
+                .set("cx", x)
+                .set("cy", y)
+                .set("r", radius * approach_scale)
+                .set("stroke", rgb(object.combo_color))
+                .set("stroke-width", 3),
+        );
+    }
+
+    group
+}
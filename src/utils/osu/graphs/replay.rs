@@ -0,0 +1,279 @@
+use crate::Error;
+use crate::utils::misc::remove_trailing_zeros;
+use crate::utils::osu::graphs::{
+    BACKGROUND_COLOR, GRID_COLOR, PANEL_COLOR, label, new_document, render_document,
+};
+use crate::utils::osu::replay::{HitError, HitWindows, Replay};
+use resvg::tiny_skia::Pixmap;
+use svg::Document;
+use svg::node::element::{Circle, Line, Rectangle};
+
+const WIDTH: u32 = 1200;
+const HEIGHT: u32 = 820;
+
+const MARGIN: f64 = 30.0;
+
+const TIMELINE_TOP: f64 = 80.0;
+const TIMELINE_HEIGHT: f64 = 260.0;
+
+const BOTTOM_TOP: f64 = 410.0;
+const BOTTOM_HEIGHT: f64 = 384.0;
+
+const PLAYFIELD_WIDTH: f64 = 512.0;
+const PLAYFIELD_HEIGHT: f64 = 384.0;
+const HEATMAP_CELL_SIZE: f64 = 8.0;
+
+const HISTOGRAM_BINS: usize = 40;
+
+const GREAT_COLOR: &str = "#66CCFF";
+const OK_COLOR: &str = "#88B300";
+const MEH_COLOR: &str = "#FFCC22";
+const MISS_COLOR: &str = "#ED1121";
+const HEATMAP_COLOR: &str = "#FF66AB";
+
+pub fn render_replay_analysis(
+    replay: &Replay,
+    hit_errors: &[HitError],
+    hit_windows: &HitWindows,
+    unstable_rate: Option<f64>,
+) -> Result<Pixmap, Error> {
+    let mut document = new_document(WIDTH, HEIGHT).add(
+        Rectangle::new()
+            .set("width", WIDTH)
+            .set("height", HEIGHT)
+            .set("fill", BACKGROUND_COLOR),
+    );
+
+    let unstable_rate = match unstable_rate {
+        Some(unstable_rate) => format!("  •  UR {}", remove_trailing_zeros(unstable_rate, 2)?),
+        None => String::new(),
+    };
+    let misses = hit_errors.iter().filter(|hit| hit.error.is_none()).count();
+    document = document.add(label(
+        format!(
+            "{}  •  {} misses{unstable_rate}",
+            replay.player_name, misses
+        ),
+        MARGIN,
+        50.0,
+        28,
+    ));
+
+    document = draw_timeline(document, hit_errors, hit_windows);
+    document = draw_histogram(document, hit_errors, hit_windows);
+    document = draw_heatmap(document, replay);
+
+    render_document(&document, WIDTH, HEIGHT)
+}
+
+fn judgement_color(error: f64, hit_windows: &HitWindows) -> &'static str {
+    let error = error.abs();
+    if error <= hit_windows.great {
+        GREAT_COLOR
+    } else if error <= hit_windows.ok {
+        OK_COLOR
+    } else {
+        MEH_COLOR
+    }
+}
+
+fn panel(x: f64, y: f64, width: f64, height: f64) -> Rectangle {
+    Rectangle::new()
+        .set("x", x)
+        .set("y", y)
+        .set("width", width)
+        .set("height", height)
+        .set("rx", 6)
+        .set("fill", PANEL_COLOR)
+}
+
+fn horizontal_line(x1: f64, x2: f64, y: f64, color: &str) -> Line {
+    Line::new()
+        .set("x1", x1)
+        .set("x2", x2)
+        .set("y1", y)
+        .set("y2", y)
+        .set("stroke", color)
+        .set("stroke-width", 1)
+}
+
+fn draw_timeline(
+    mut document: Document,
+    hit_errors: &[HitError],
+    hit_windows: &HitWindows,
+) -> Document {
+    let width = f64::from(WIDTH) - MARGIN * 2.0;
+    let middle = TIMELINE_TOP + TIMELINE_HEIGHT / 2.0;
+    let scale = (TIMELINE_HEIGHT / 2.0 - 10.0) / hit_windows.meh;
+
+    document = document
+        .add(panel(MARGIN, TIMELINE_TOP, width, TIMELINE_HEIGHT))
+        .add(label("Late", MARGIN + 10.0, TIMELINE_TOP + 22.0, 14))
+        .add(label(
+            "Early",
+            MARGIN + 10.0,
+            TIMELINE_TOP + TIMELINE_HEIGHT - 12.0,
+            14,
+        ));
+
+    for window in [hit_windows.great, hit_windows.ok] {
+        document = document
+            .add(horizontal_line(
+                MARGIN,
+                MARGIN + width,
+                middle - window * scale,
+                GRID_COLOR,
+            ))
+            .add(horizontal_line(
+                MARGIN,
+                MARGIN + width,
+                middle + window * scale,
+                GRID_COLOR,
+            ));
+    }
+    document = document.add(horizontal_line(MARGIN, MARGIN + width, middle, "white"));
+
+    let (Some(first), Some(last)) = (hit_errors.first(), hit_errors.last()) else {
+        return document;
+    };
+    let duration = (last.time - first.time).max(1.0);
+
+    for hit in hit_errors {
+        let x = MARGIN + 5.0 + (hit.time - first.time) / duration * (width - 10.0);
+        document = match hit.error {
+            Some(error) => document.add(
+                Circle::new()
+                    .set("cx", x)
+                    // Late hits have a positive error and go above the middle line.
+                    .set("cy", middle - error * scale)
+                    .set("r", 2.5)
+                    .set("fill", judgement_color(error, hit_windows)),
+            ),
+            None => document.add(
+                Line::new()
+                    .set("x1", x)
+                    .set("x2", x)
+                    .set("y1", TIMELINE_TOP)
+                    .set("y2", TIMELINE_TOP + TIMELINE_HEIGHT)
+                    .set("stroke", MISS_COLOR)
+                    .set("stroke-width", 2)
+                    .set("opacity", 0.7),
+            ),
+        };
+    }
+
+    document
+}
+
+fn draw_histogram(
+    mut document: Document,
+    hit_errors: &[HitError],
+    hit_windows: &HitWindows,
+) -> Document {
+    let width = f64::from(WIDTH) - MARGIN * 3.0 - PLAYFIELD_WIDTH;
+    let bin_width = width / HISTOGRAM_BINS as f64;
+    let bin_size = hit_windows.meh * 2.0 / HISTOGRAM_BINS as f64;
+
+    document = document
+        .add(label("Hit error", MARGIN, BOTTOM_TOP - 15.0, 20))
+        .add(panel(MARGIN, BOTTOM_TOP, width, BOTTOM_HEIGHT))
+        .add(label(
+            format!("-{}ms", hit_windows.meh.round()),
+            MARGIN + 8.0,
+            BOTTOM_TOP + BOTTOM_HEIGHT - 10.0,
+            14,
+        ))
+        .add(label(
+            format!("+{}ms", hit_windows.meh.round()),
+            MARGIN + width - 60.0,
+            BOTTOM_TOP + BOTTOM_HEIGHT - 10.0,
+            14,
+        ));
+
+    let mut bins = [0_u32; HISTOGRAM_BINS];
+    for error in hit_errors.iter().filter_map(|hit| hit.error) {
+        let bin = ((error + hit_windows.meh) / bin_size).floor();
+        if bin >= 0.0 {
+            bins[(bin as usize).min(HISTOGRAM_BINS - 1)] += 1;
+        }
+    }
+
+    let Some(max_count) = bins.iter().max().copied().filter(|count| *count > 0) else {
+        return document;
+    };
+
+    let bar_area = BOTTOM_HEIGHT - 40.0;
+    let baseline = BOTTOM_TOP + BOTTOM_HEIGHT - 30.0;
+    for (index, count) in bins.iter().enumerate() {
+        if *count == 0 {
+            continue;
+        }
+        let height = f64::from(*count) / f64::from(max_count) * bar_area;
+        let center = -hit_windows.meh + (index as f64 + 0.5) * bin_size;
+        document = document.add(
+            Rectangle::new()
+                .set("x", MARGIN + index as f64 * bin_width + 1.0)
+                .set("y", baseline - height)
+                .set("width", bin_width - 2.0)
+                .set("height", height)
+                .set("fill", judgement_color(center, hit_windows)),
+        );
+    }
+
+    document.add(
+        Line::new()
+            .set("x1", MARGIN + width / 2.0)
+            .set("x2", MARGIN + width / 2.0)
+            .set("y1", BOTTOM_TOP)
+            .set("y2", baseline)
+            .set("stroke", "white")
+            .set("stroke-width", 1)
+            .set("opacity", 0.5),
+    )
+}
+
+fn draw_heatmap(mut document: Document, replay: &Replay) -> Document {
+    let x = f64::from(WIDTH) - MARGIN - PLAYFIELD_WIDTH;
+
+    document = document
+        .add(label("Cursor heatmap", x, BOTTOM_TOP - 15.0, 20))
+        .add(panel(x, BOTTOM_TOP, PLAYFIELD_WIDTH, PLAYFIELD_HEIGHT));
+
+    let columns = (PLAYFIELD_WIDTH / HEATMAP_CELL_SIZE) as usize;
+    let rows = (PLAYFIELD_HEIGHT / HEATMAP_CELL_SIZE) as usize;
+    let mut cells = vec![0_u32; columns * rows];
+    for frame in &replay.frames {
+        let (frame_x, frame_y) = (f64::from(frame.x), f64::from(frame.y));
+        if !(0.0..PLAYFIELD_WIDTH).contains(&frame_x) || !(0.0..PLAYFIELD_HEIGHT).contains(&frame_y)
+        {
+            continue;
+        }
+        let column = (frame_x / HEATMAP_CELL_SIZE) as usize;
+        let row = (frame_y / HEATMAP_CELL_SIZE) as usize;
+        cells[row * columns + column] += 1;
+    }
+
+    let Some(max_count) = cells.iter().max().copied().filter(|count| *count > 0) else {
+        return document;
+    };
+    let max_intensity = f64::from(max_count).ln_1p();
+
+    for (index, count) in cells.iter().enumerate() {
+        if *count == 0 {
+            continue;
+        }
+        let column = (index % columns) as f64;
+        let row = (index / columns) as f64;
+        document = document.add(
+            Rectangle::new()
+                .set("x", x + column * HEATMAP_CELL_SIZE)
+                .set("y", BOTTOM_TOP + row * HEATMAP_CELL_SIZE)
+                .set("width", HEATMAP_CELL_SIZE)
+                .set("height", HEATMAP_CELL_SIZE)
+                .set("fill", HEATMAP_COLOR)
+                .set("opacity", f64::from(*count).ln_1p() / max_intensity),
+        );
+    }
+
+    document
+}
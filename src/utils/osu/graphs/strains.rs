@@ -0,0 +1,277 @@
+use crate::Error;
+use crate::utils::osu::graphs::{
+    BACKGROUND_COLOR, GRID_COLOR, PANEL_COLOR, label, new_document, render_document,
+};
+use resvg::tiny_skia::Pixmap;
+use rosu_pp::any::Strains;
+use rosu_pp::{Beatmap, Difficulty, GameMods};
+use rosu_v2::model::GameMode;
+use svg::Document;
+use svg::node::element::path::Data;
+use svg::node::element::{Path, Rectangle};
+
+const WIDTH: u32 = 1200;
+const HEIGHT: u32 = 420;
+const MARGIN: f64 = 30.0;
+
+const CHART_TOP: f64 = 80.0;
+const CHART_BOTTOM: f64 = 370.0;
+
+const KIAI_COLOR: &str = "#FFCC22";
+const BREAK_COLOR: &str = "#8C7A82";
+
+pub struct StrainCurve {
+    pub name: &'static str,
+    pub color: &'static str,
+    pub values: Vec<f64>,
+}
+
+/// Strains of a beatmap split into sections, with kiai and break sections in milliseconds.
+/// Every time is already adjusted for the clock rate of the mods.
+pub struct MapStrains {
+    pub curves: Vec<StrainCurve>,
+    pub section_length: f64,
+    pub kiai: Vec<(f64, f64)>,
+    pub breaks: Vec<(f64, f64)>,
+}
+
+impl MapStrains {
+    fn length(&self) -> f64 {
+        self.curves
+            .iter()
+            .map(|curve| curve.values.len())
+            .max()
+            .unwrap_or(0) as f64
+            * self.section_length
+    }
+}
+
+pub fn get_map_strains(file: &[u8], mode: GameMode, mods: &GameMods) -> Result<MapStrains, Error> {
+    let pp_mode = match mode {
+        GameMode::Osu => rosu_pp::model::mode::GameMode::Osu,
+        GameMode::Taiko => rosu_pp::model::mode::GameMode::Taiko,
+        GameMode::Catch => rosu_pp::model::mode::GameMode::Catch,
+        GameMode::Mania => rosu_pp::model::mode::GameMode::Mania,
+    };
+
+    let binding = Beatmap::from_bytes(file)?;
+    let map = binding.convert(pp_mode, mods)?;
+
+    let clock_rate = map.attributes().mods(mods.clone()).build().clock_rate;
+    let strains = Difficulty::new().mods(mods.clone()).strains(&map);
+    let section_length = strains.section_len();
+
+    let curves = match strains {
+        Strains::Osu(strains) => vec![
+            StrainCurve {
+                name: "Aim",
+                color: "#FF66AB",
+                values: strains.aim,
+            },
+            StrainCurve {
+                name: "Speed",
+                color: "#66CCFF",
+                values: strains.speed,
+            },
+        ],
+        Strains::Taiko(strains) => vec![
+            StrainCurve {
+                name: "Color",
+                color: "#FF66AB",
+                values: strains.color,
+            },
+            StrainCurve {
+                name: "Rhythm",
+                color: "#66CCFF",
+                values: strains.rhythm,
+            },
+            StrainCurve {
+                name: "Stamina",
+                color: "#88B300",
+                values: strains.stamina,
+            },
+        ],
+        Strains::Catch(strains) => vec![StrainCurve {
+            name: "Movement",
+            color: "#FF66AB",
+            values: strains.movement,
+        }],
+        Strains::Mania(strains) => vec![StrainCurve {
+            name: "Strain",
+            color: "#FF66AB",
+            values: strains.strains,
+        }],
+    };
+
+    let breaks = map
+        .breaks
+        .iter()
+        .map(|period| (period.start_time / clock_rate, period.end_time / clock_rate))
+        .collect();
+
+    // Effect points only mark where kiai gets toggled, so pair every start with the next end.
+    let mut kiai = Vec::new();
+    let mut kiai_start = None;
+    for point in &map.effect_points {
+        match (point.kiai, kiai_start) {
+            (true, None) => kiai_start = Some(point.time / clock_rate),
+            (false, Some(start)) => {
+                kiai.push((start, point.time / clock_rate));
+                kiai_start = None;
+            }
+            _ => {}
+        }
+    }
+
+    let mut map_strains = MapStrains {
+        curves,
+        section_length,
+        kiai,
+        breaks,
+    };
+
+    if let Some(start) = kiai_start {
+        let end = map_strains.length();
+        map_strains.kiai.push((start, end));
+    }
+
+    Ok(map_strains)
+}
+
+fn format_time(milliseconds: f64) -> String {
+    let seconds = (milliseconds / 1000.0).round() as i64;
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+fn draw_sections(
+    mut document: Document,
+    sections: &[(f64, f64)],
+    to_x: impl Fn(f64) -> f64,
+    color: &str,
+) -> Document {
+    for (start, end) in sections {
+        document = document.add(
+            Rectangle::new()
+                .set("x", to_x(*start))
+                .set("y", CHART_TOP)
+                .set("width", (to_x(*end) - to_x(*start)).max(1.0))
+                .set("height", CHART_BOTTOM - CHART_TOP)
+                .set("fill", color)
+                .set("fill-opacity", 0.2),
+        );
+    }
+
+    document
+}
+
+pub fn render_strains(title: &str, strains: &MapStrains) -> Result<Pixmap, Error> {
+    let length = strains.length();
+    if length <= 0.0 {
+        return Err(Error::from("The beatmap doesn't have any strains to draw"));
+    }
+
+    let max_strain = strains
+        .curves
+        .iter()
+        .flat_map(|curve| curve.values.iter().copied())
+        .fold(0.0, f64::max);
+    let max_strain = if max_strain > f64::EPSILON {
+        max_strain
+    } else {
+        1.0
+    };
+
+    let left = MARGIN;
+    let right = f64::from(WIDTH) - MARGIN;
+    let to_x = |time: f64| left + time / length * (right - left);
+
+    let mut document = new_document(WIDTH, HEIGHT)
+        .add(
+            Rectangle::new()
+                .set("width", WIDTH)
+                .set("height", HEIGHT)
+                .set("fill", BACKGROUND_COLOR),
+        )
+        .add(
+            Rectangle::new()
+                .set("x", left)
+                .set("y", CHART_TOP)
+                .set("width", right - left)
+                .set("height", CHART_BOTTOM - CHART_TOP)
+                .set("rx", 6)
+                .set("fill", PANEL_COLOR),
+        )
+        .add(label(title, MARGIN, 45.0, 24));
+
+    document = draw_sections(document, &strains.breaks, to_x, BREAK_COLOR);
+    document = draw_sections(document, &strains.kiai, to_x, KIAI_COLOR);
+
+    for step in 0..=4 {
+        let time = length * f64::from(step) / 4.0;
+        let anchor = match step {
+            0 => "start",
+            4 => "end",
+            _ => "middle",
+        };
+        document = document
+            .add(
+                Rectangle::new()
+                    .set("x", to_x(time))
+                    .set("y", CHART_TOP)
+                    .set("width", 1)
+                    .set("height", CHART_BOTTOM - CHART_TOP)
+                    .set("fill", GRID_COLOR),
+            )
+            .add(
+                label(format_time(time), to_x(time), CHART_BOTTOM + 22.0, 14)
+                    .set("text-anchor", anchor),
+            );
+    }
+
+    for curve in &strains.curves {
+        let mut data = Data::new();
+        for (index, value) in curve.values.iter().enumerate() {
+            let x = to_x((index as f64 + 0.5) * strains.section_length);
+            let y = CHART_BOTTOM - value / max_strain * (CHART_BOTTOM - CHART_TOP - 10.0);
+            data = if index == 0 {
+                data.move_to((x, y))
+            } else {
+                data.line_to((x, y))
+            };
+        }
+
+        document = document.add(
+            Path::new()
+                .set("d", data)
+                .set("fill", "none")
+                .set("stroke", curve.color)
+                .set("stroke-width", 2)
+                .set("stroke-linejoin", "round"),
+        );
+    }
+
+    // Legend in the top right, drawn from right to left.
+    let mut legend_x = right;
+    let legend = strains
+        .curves
+        .iter()
+        .map(|curve| (curve.name, curve.color))
+        .chain([("Kiai", KIAI_COLOR), ("Break", BREAK_COLOR)])
+        .collect::<Vec<(&str, &str)>>();
+    for (name, color) in legend.into_iter().rev() {
+        legend_x -= 12.0 * name.len() as f64 + 30.0;
+        document = document
+            .add(
+                Rectangle::new()
+                    .set("x", legend_x)
+                    .set("y", 32.0)
+                    .set("width", 14)
+                    .set("height", 14)
+                    .set("rx", 3)
+                    .set("fill", color),
+            )
+            .add(label(name, legend_x + 20.0, 45.0, 16));
+    }
+
+    render_document(&document, WIDTH, HEIGHT)
+}
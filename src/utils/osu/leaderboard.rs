@@ -0,0 +1,173 @@
+use crate::models::linked_osu_profiles::LinkedOsuProfile;
+use crate::models::osu_users::{NewOsuUser, OsuUser};
+use crate::utils::db::linked_osu_profiles;
+use crate::utils::misc::remove_trailing_zeros;
+use crate::utils::osu::misc_format::format_user_link;
+use crate::utils::osu::pp::CalculateResults;
+use crate::utils::osu::score_format::format_server_score;
+use crate::{Context, Error};
+use diesel_async::AsyncPgConnection;
+use num_format::{Locale, ToFormattedString};
+use poise::serenity_prelude::UserId;
+use rosu_v2::prelude::Score;
+use std::cmp::Ordering;
+
+#[derive(poise::ChoiceParameter, Clone, Copy)]
+pub enum LeaderboardSortChoices {
+    #[name = "PP"]
+    Pp,
+    #[name = "Accuracy"]
+    #[name = "Acc"]
+    Accuracy,
+    #[name = "Rank"]
+    #[name = "Global rank"]
+    GlobalRank,
+    #[name = "Score"]
+    #[name = "Ranked score"]
+    RankedScore,
+    Playcount,
+}
+
+pub struct LeaderboardEntry {
+    pub discord_id: i64,
+    pub osu_id: i64,
+    pub username: String,
+    pub pp: f64,
+    pub accuracy: f64,
+    pub global_rank: i32,
+    pub ranked_score: i64,
+    pub playcount: i32,
+}
+
+impl LeaderboardEntry {
+    pub fn from_osu_user(discord_id: i64, user: &OsuUser) -> LeaderboardEntry {
+        LeaderboardEntry {
+            discord_id,
+            osu_id: user.id,
+            username: user.username.clone(),
+            pp: user.pp,
+            accuracy: user.accuracy,
+            global_rank: user.global_rank,
+            ranked_score: user.ranked_score,
+            playcount: user.playcount,
+        }
+    }
+
+    pub fn from_new_osu_user(discord_id: i64, user: &NewOsuUser) -> LeaderboardEntry {
+        LeaderboardEntry {
+            discord_id,
+            osu_id: user.id,
+            username: user.username.clone(),
+            pp: user.pp,
+            accuracy: user.accuracy,
+            global_rank: user.global_rank,
+            ranked_score: user.ranked_score,
+            playcount: user.playcount,
+        }
+    }
+}
+
+pub struct ServerScore {
+    pub discord_id: i64,
+    pub osu_id: i64,
+    pub username: String,
+    pub score: Score,
+    pub pp: CalculateResults,
+}
+
+pub async fn get_guild_profiles(
+    ctx: Context<'_>,
+    connection: &mut AsyncPgConnection,
+) -> Result<Vec<LinkedOsuProfile>, Error> {
+    let linked_profiles = linked_osu_profiles::get_all(connection).await?;
+
+    let guild = ctx
+        .guild()
+        .ok_or("Failed to get guild in get_guild_profiles")?;
+
+    Ok(linked_profiles
+        .into_iter()
+        // Alts are left out, so every member only shows up once.
+        .filter(|profile| {
+            profile.is_primary
+                && u64::try_from(profile.id)
+                    .is_ok_and(|id| guild.members.contains_key(&UserId::new(id)))
+        })
+        .collect())
+}
+
+pub fn sort_leaderboard(entries: &mut [LeaderboardEntry], sort_type: LeaderboardSortChoices) {
+    match sort_type {
+        LeaderboardSortChoices::Pp => entries.sort_by(|a, b| b.pp.total_cmp(&a.pp)),
+        LeaderboardSortChoices::Accuracy => {
+            entries.sort_by(|a, b| b.accuracy.total_cmp(&a.accuracy));
+        }
+        // Unranked users have a global rank of 0, and belong at the bottom.
+        LeaderboardSortChoices::GlobalRank => {
+            entries.sort_by(|a, b| match (a.global_rank == 0, b.global_rank == 0) {
+                (true, false) => Ordering::Greater,
+                (false, true) => Ordering::Less,
+                _ => a.global_rank.cmp(&b.global_rank),
+            })
+        }
+        LeaderboardSortChoices::RankedScore => {
+            entries.sort_by(|a, b| b.ranked_score.cmp(&a.ranked_score));
+        }
+        LeaderboardSortChoices::Playcount => entries.sort_by(|a, b| b.playcount.cmp(&a.playcount)),
+    }
+}
+
+pub fn format_leaderboard(
+    entries: &[LeaderboardEntry],
+    sort_type: LeaderboardSortChoices,
+) -> Result<Vec<String>, Error> {
+    let mut formatted = Vec::new();
+    for (position, entry) in entries.iter().enumerate() {
+        let value = match sort_type {
+            LeaderboardSortChoices::Pp => format!("{}pp", remove_trailing_zeros(entry.pp, 2)?),
+            LeaderboardSortChoices::Accuracy => {
+                format!("{}%", remove_trailing_zeros(entry.accuracy, 2)?)
+            }
+            LeaderboardSortChoices::GlobalRank => {
+                if entry.global_rank == 0 {
+                    String::from("Unranked")
+                } else {
+                    format!("#{}", entry.global_rank.to_formatted_string(&Locale::en))
+                }
+            }
+            LeaderboardSortChoices::RankedScore => {
+                entry.ranked_score.to_formatted_string(&Locale::en)
+            }
+            LeaderboardSortChoices::Playcount => {
+                format!("{} plays", entry.playcount.to_formatted_string(&Locale::en))
+            }
+        };
+
+        formatted.push(format!(
+            "{}. [{}]({}) (<@{}>) - **{}**",
+            position + 1,
+            entry.username,
+            format_user_link(entry.osu_id),
+            entry.discord_id,
+            value
+        ));
+    }
+
+    Ok(formatted)
+}
+
+pub fn format_server_scores(scores: &[ServerScore]) -> Result<Vec<String>, Error> {
+    let mut formatted = Vec::new();
+    for (position, server_score) in scores.iter().enumerate() {
+        formatted.push(format!(
+            "{}. [{}]({}) (<@{}>)\n{}",
+            position + 1,
+            server_score.username,
+            format_user_link(server_score.osu_id),
+            server_score.discord_id,
+            format_server_score(&server_score.score, &server_score.pp)?
+        ));
+    }
+
+    Ok(formatted)
+}
@@ -0,0 +1,223 @@
+use crate::models::osu_map_feed_cursors::NewOsuMapFeedCursor;
+use crate::models::osu_map_feeds::OsuMapFeed;
+use crate::utils::db::{osu_guild_channels, osu_map_feed_cursors, osu_map_feeds};
+use crate::utils::osu::caching::get_updated_beatmapset;
+use crate::utils::osu::map_format::format_map_status;
+use crate::utils::osu::misc_format::format_rank_status;
+use crate::{Error, Pool};
+use chrono::{DateTime, Duration, Utc};
+use diesel_async::AsyncPgConnection;
+use diesel_async::pooled_connection::AsyncDieselConnectionManager;
+use poise::serenity_prelude::model::colour::colours::roles::BLUE;
+use poise::serenity_prelude::{CreateMessage, GenericChannelId, Http};
+use rosu_v2::Osu;
+use rosu_v2::prelude::{BeatmapsetExtended, BeatmapsetSearchSort, RankStatus};
+use std::env;
+use std::sync::{Arc, OnceLock};
+use tracing::error;
+
+static MAP_FEED_INTERVAL: OnceLock<u64> = OnceLock::new();
+
+// Beatmapsets have to stay qualified for at least this long before they can be ranked.
+const QUALIFIED_DAYS: i64 = 7;
+
+pub struct MapFeed {
+    pub http: Arc<Http>,
+    pub osu_client: Arc<Osu>,
+    pub pool: Pool<AsyncDieselConnectionManager<AsyncPgConnection>>,
+}
+
+impl MapFeed {
+    pub async fn feed_loop(&mut self) -> Result<(), Error> {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(
+            MAP_FEED_INTERVAL
+                .get_or_init(|| {
+                    env::var("MAP_FEED_INTERVAL")
+                        .unwrap_or_else(|_| String::from("300"))
+                        .parse::<u64>()
+                        .expect("Failed to parse map feed interval.")
+                })
+                .to_owned(),
+        ));
+        loop {
+            interval.tick().await;
+            let connection = &mut match self.pool.get().await {
+                Ok(connection) => connection,
+                Err(why) => {
+                    error!("Failed to connect to database {}", why);
+                    continue;
+                }
+            };
+            for status in [RankStatus::Qualified, RankStatus::Ranked, RankStatus::Loved] {
+                if let Err(why) = self.check_status(connection, status).await {
+                    error!("Error occurred while running map feed: {}", why);
+                }
+            }
+        }
+    }
+
+    async fn check_status(
+        &mut self,
+        connection: &mut AsyncPgConnection,
+        status: RankStatus,
+    ) -> Result<(), Error> {
+        let status_name = format_rank_status(status);
+
+        let mut mapsets = self
+            .osu_client
+            .beatmapset_search()
+            .status(Some(status))
+            .sort(BeatmapsetSearchSort::RankedDate, true)
+            .await?
+            .mapsets
+            .into_iter()
+            .filter_map(|mapset| {
+                let ranked_date =
+                    DateTime::from_timestamp(mapset.ranked_date?.unix_timestamp(), 0)?;
+                Some((ranked_date, mapset))
+            })
+            .collect::<Vec<(DateTime<Utc>, BeatmapsetExtended)>>();
+        mapsets.sort_by_key(|(ranked_date, _)| *ranked_date);
+
+        let Some(cursor) = osu_map_feed_cursors::read(connection, &status_name).await? else {
+            // Nothing to compare against on the first run, so start from the newest set instead
+            // of posting the whole first page.
+            if let Some((newest_date, _)) = mapsets.last() {
+                let item = NewOsuMapFeedCursor {
+                    status: status_name,
+                    last_date: *newest_date,
+                    last_ids: mapsets
+                        .iter()
+                        .filter(|(ranked_date, _)| ranked_date == newest_date)
+                        .map(|(_, mapset)| Some(i64::from(mapset.mapset_id)))
+                        .collect(),
+                };
+                osu_map_feed_cursors::create(connection, &item).await?;
+            }
+            return Ok(());
+        };
+
+        let feeds = osu_map_feeds::get_all(connection).await?;
+
+        let mut item = NewOsuMapFeedCursor {
+            status: status_name.clone(),
+            last_date: cursor.last_date,
+            last_ids: cursor.last_ids,
+        };
+
+        for (ranked_date, mapset) in mapsets {
+            let mapset_id = i64::from(mapset.mapset_id);
+            if ranked_date < item.last_date
+                || (ranked_date == item.last_date && item.last_ids.contains(&Some(mapset_id)))
+            {
+                continue;
+            }
+
+            let matching_feeds = feeds
+                .iter()
+                .filter(|feed| feed_matches(feed, &mapset, status))
+                .collect::<Vec<&OsuMapFeed>>();
+
+            if !matching_feeds.is_empty() {
+                let beatmapset =
+                    get_updated_beatmapset(connection, self.osu_client.clone(), mapset.mapset_id)
+                        .await?;
+
+                let mut embed = format_map_status(beatmapset, BLUE)?
+                    .title(format!("Newly {}", status_name.to_lowercase()));
+
+                if status == RankStatus::Qualified {
+                    embed = embed.field(
+                        "Earliest ranking",
+                        format!(
+                            "<t:{}:R>",
+                            (ranked_date + Duration::days(QUALIFIED_DAYS)).timestamp()
+                        ),
+                        false,
+                    );
+                }
+
+                for feed in matching_feeds {
+                    let Ok(guild_channels) =
+                        osu_guild_channels::read(connection, feed.guild_id).await
+                    else {
+                        continue;
+                    };
+
+                    for map_channel in guild_channels
+                        .map_channel
+                        .iter()
+                        .flatten()
+                        .flatten()
+                        .copied()
+                        .collect::<Vec<i64>>()
+                    {
+                        let builder = CreateMessage::new().embed(embed.clone());
+
+                        if let Err(why) = GenericChannelId::from(u64::try_from(map_channel)?)
+                            .send_message(&self.http, builder)
+                            .await
+                        {
+                            error!(
+                                "Failed to post map feed in guild {}: {}",
+                                feed.guild_id, why
+                            );
+                        }
+                    }
+                }
+            }
+
+            if ranked_date > item.last_date {
+                item.last_date = ranked_date;
+                item.last_ids.clear();
+            }
+            item.last_ids.push(Some(mapset_id));
+
+            // Saved after every set, so a restart halfway through doesn't post anything twice.
+            osu_map_feed_cursors::create(connection, &item).await?;
+        }
+
+        Ok(())
+    }
+}
+
+fn feed_matches(feed: &OsuMapFeed, mapset: &BeatmapsetExtended, status: RankStatus) -> bool {
+    let status_enabled = match status {
+        RankStatus::Qualified => feed.qualified,
+        RankStatus::Ranked | RankStatus::Approved => feed.ranked,
+        RankStatus::Loved => feed.loved,
+        _ => false,
+    };
+    if !status_enabled {
+        return false;
+    }
+
+    if let Some(tags) = &feed.tags {
+        let mapset_tags = mapset.tags.to_lowercase();
+        let mut feed_tags = tags.split_whitespace().peekable();
+        if feed_tags.peek().is_some()
+            && !feed_tags.any(|tag| {
+                mapset_tags
+                    .split_whitespace()
+                    .any(|mapset_tag| mapset_tag == tag.to_lowercase())
+            })
+        {
+            return false;
+        }
+    }
+
+    mapset.maps.iter().flatten().any(|map| {
+        feed.mode
+            .as_ref()
+            .is_none_or(|mode| *mode == map.mode.to_string())
+            && feed
+                .min_stars
+                .is_none_or(|min_stars| f64::from(map.stars) >= min_stars)
+            && feed
+                .max_stars
+                .is_none_or(|max_stars| f64::from(map.stars) <= max_stars)
+            && feed
+                .max_length
+                .is_none_or(|max_length| i64::from(map.seconds_drain) <= i64::from(max_length))
+    })
+}
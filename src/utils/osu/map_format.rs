@@ -14,6 +14,9 @@ use std::sync::OnceLock;
 
 static MAX_DIFF_LENGTH: OnceLock<usize> = OnceLock::new();
 
+// Embed field values are capped at 1024 characters, code block included.
+const MAX_UPDATE_DIFF_LENGTH: usize = 1000;
+
 fn get_max_diff_length() -> usize {
     MAX_DIFF_LENGTH
         .get_or_init(|| {
@@ -186,6 +189,127 @@ pub fn format_beatmapset(mut beatmaps: Vec<(Beatmap, OsuFile)>) -> Result<String
     Ok(formatted_beatmaps)
 }
 
+fn shorten_version(version: &str) -> String {
+    let max_diff_length = get_max_diff_length();
+    if version.len() < max_diff_length {
+        version.to_owned()
+    } else {
+        let substring: String = version.chars().take(max_diff_length - 3).collect();
+        substring + "..."
+    }
+}
+
+fn format_drain(drain: i32) -> String {
+    format!("{}:{:02}", drain / 60, drain % 60)
+}
+
+fn diff_beatmap(old: &Beatmap, new: &Beatmap) -> Result<Vec<String>, Error> {
+    let mut changes = Vec::new();
+
+    for (name, old_value, new_value) in [
+        (
+            "Stars",
+            Some(old.difficulty_rating),
+            Some(new.difficulty_rating),
+        ),
+        ("AR", Some(old.ar), Some(new.ar)),
+        ("OD", old.od, new.od),
+        ("CS", Some(old.cs), Some(new.cs)),
+        ("HP", old.hp, new.hp),
+        ("BPM", Some(old.bpm), Some(new.bpm)),
+    ] {
+        // Rows cached before OD and HP were stored don't have them, which isn't a change.
+        let (Some(old_value), Some(new_value)) = (old_value, new_value) else {
+            continue;
+        };
+        let old_value = remove_trailing_zeros(old_value, 2)?;
+        let new_value = remove_trailing_zeros(new_value, 2)?;
+        if (old_value - new_value).abs() > f64::EPSILON {
+            changes.push(format!("  {name:<9}{old_value:<8}→ {new_value}"));
+        }
+    }
+
+    if old.drain != new.drain {
+        changes.push(format!(
+            "  {:<9}{:<8}→ {}",
+            "Drain",
+            format_drain(old.drain),
+            format_drain(new.drain)
+        ));
+    }
+
+    for (name, old_value, new_value) in [
+        ("Circles", old.count_circles, new.count_circles),
+        ("Sliders", old.count_sliders, new.count_sliders),
+        ("Spinners", old.count_spinners, new.count_spinners),
+    ] {
+        if old_value != new_value {
+            changes.push(format!("  {name:<9}{old_value:<8}→ {new_value}"));
+        }
+    }
+
+    Ok(changes)
+}
+
+/// Compares the difficulties of a beatmapset cached before an update with the updated ones.
+/// Returns None if nothing changed, or if nothing was cached to compare against.
+pub fn format_beatmapset_diff(
+    old: &[Beatmap],
+    new: &[(Beatmap, OsuFile)],
+) -> Result<Option<String>, Error> {
+    if old.is_empty() {
+        return Ok(None);
+    }
+
+    let mut sections = Vec::new();
+
+    for (beatmap, _) in new {
+        if let Some(old_beatmap) = old.iter().find(|old_beatmap| old_beatmap.id == beatmap.id) {
+            let changes = diff_beatmap(old_beatmap, beatmap)?;
+            if !changes.is_empty() {
+                sections.push(format!(
+                    "{}\n{}",
+                    shorten_version(&beatmap.version),
+                    changes.join("\n")
+                ));
+            }
+        } else {
+            sections.push(format!(
+                "+ {} ({}★)",
+                shorten_version(&beatmap.version),
+                remove_trailing_zeros(beatmap.difficulty_rating, 2)?
+            ));
+        }
+    }
+
+    for old_beatmap in old {
+        if !new.iter().any(|(beatmap, _)| beatmap.id == old_beatmap.id) {
+            sections.push(format!(
+                "- {} ({}★)",
+                shorten_version(&old_beatmap.version),
+                remove_trailing_zeros(old_beatmap.difficulty_rating, 2)?
+            ));
+        }
+    }
+
+    if sections.is_empty() {
+        return Ok(None);
+    }
+
+    let mut formatted = String::from("```diff\n");
+    for (index, section) in sections.iter().enumerate() {
+        if formatted.len() + section.len() > MAX_UPDATE_DIFF_LENGTH {
+            let _ = writeln!(formatted, "...and {} more", sections.len() - index);
+            break;
+        }
+        formatted.push_str(section);
+        formatted.push('\n');
+    }
+    formatted.push_str("```");
+
+    Ok(Some(formatted))
+}
+
 pub fn format_map_status(
     beatmapset_and_beatmap: (Beatmapset, Vec<(Beatmap, OsuFile)>),
     color: Color,
@@ -217,3 +341,24 @@ pub fn format_map_status(
         .description(description)
         .author(created_author))
 }
+
+pub fn format_search_result(beatmap: &Beatmap, beatmapset: &Beatmapset) -> Result<String, Error> {
+    let mode = gamemode_from_string(&beatmap.mode)
+        .ok_or("Failed to parse beatmap mode in format_search_result")?;
+
+    Ok(format!(
+        "[{} - {} [{}]]({})\n{} {}★ • {} BPM • {}:{:02} • AR {} • CS {} • {}",
+        beatmapset.artist,
+        beatmapset.title,
+        beatmap.version,
+        format_beatmap_link(Some(beatmap.id), beatmapset.id, Some(&beatmap.mode)),
+        format_mode_abbreviation(mode),
+        remove_trailing_zeros(beatmap.difficulty_rating, 2)?,
+        remove_trailing_zeros(beatmap.bpm, 2)?,
+        beatmap.drain / 60,
+        beatmap.drain % 60,
+        remove_trailing_zeros(beatmap.ar, 1)?,
+        remove_trailing_zeros(beatmap.cs, 1)?,
+        beatmap.status
+    ))
+}
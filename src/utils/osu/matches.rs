@@ -0,0 +1,445 @@
+use crate::models::beatmaps::Beatmap;
+use crate::models::beatmapsets::Beatmapset;
+use crate::models::osu_files::OsuFile;
+use crate::utils::misc::remove_trailing_zeros;
+use crate::utils::osu::caching::get_beatmap;
+use crate::utils::osu::calculate::{SimulatedScore, calculate_simulated};
+use crate::utils::osu::misc_format::{format_beatmap_link, format_user_link};
+use crate::{Error, Pool};
+use dashmap::DashMap;
+use diesel_async::AsyncPgConnection;
+use diesel_async::pooled_connection::AsyncDieselConnectionManager;
+use foldhash::{HashMap, HashMapExt, HashSet, HashSetExt};
+use num_format::{Locale, ToFormattedString};
+use poise::serenity_prelude::model::colour::colours::roles::BLUE;
+use poise::serenity_prelude::{
+    CreateEmbed, CreateEmbedFooter, CreateMessage, GenericChannelId, Http,
+};
+use rosu_v2::Osu;
+use rosu_v2::error::OsuError;
+use rosu_v2::prelude::{MatchEvent, MatchGame, MatchScore, OsuMatch, Team, TeamType, User};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+use tracing::{error, info};
+
+pub static TRACKED_MATCHES: OnceLock<DashMap<u32, GenericChannelId>> = OnceLock::new();
+
+const MATCH_POLL_INTERVAL: Duration = Duration::from_secs(20);
+// Tracking gives up once the API fails this many times in a row.
+const MAX_POLL_FAILURES: u32 = 5;
+
+pub struct MatchTracker {
+    pub http: Arc<Http>,
+    pub osu_client: Arc<Osu>,
+    pub pool: Pool<AsyncDieselConnectionManager<AsyncPgConnection>>,
+    pub match_id: u32,
+    pub channel_id: GenericChannelId,
+    pub posted_games: HashSet<u64>,
+    pub red_wins: u32,
+    pub blue_wins: u32,
+}
+
+impl MatchTracker {
+    pub fn new(
+        http: Arc<Http>,
+        osu_client: Arc<Osu>,
+        pool: Pool<AsyncDieselConnectionManager<AsyncPgConnection>>,
+        match_id: u32,
+        channel_id: GenericChannelId,
+    ) -> MatchTracker {
+        MatchTracker {
+            http,
+            osu_client,
+            pool,
+            match_id,
+            channel_id,
+            posted_games: HashSet::new(),
+            red_wins: 0,
+            blue_wins: 0,
+        }
+    }
+
+    pub async fn tracking_loop(&mut self) -> Result<(), Error> {
+        let result = self.poll_match().await;
+
+        TRACKED_MATCHES
+            .get_or_init(DashMap::new)
+            .remove(&self.match_id);
+
+        info!("Stopped tracking match {}", self.match_id);
+
+        result
+    }
+
+    async fn poll_match(&mut self) -> Result<(), Error> {
+        let osu_match = self.osu_client.osu_match(self.match_id).await?;
+
+        // Games that were already finished before tracking started shouldn't be posted, but
+        // they still count towards the running team score.
+        for game in finished_games(&osu_match) {
+            self.posted_games.insert(game.game_id);
+            self.add_team_win(game);
+        }
+
+        let mut cursor = next_cursor(&osu_match);
+        if osu_match.match_info.end_time.is_some() {
+            return Ok(());
+        }
+
+        let match_name = osu_match.match_info.name.clone();
+
+        let mut interval = tokio::time::interval(MATCH_POLL_INTERVAL);
+        interval.tick().await;
+
+        let mut failures = 0;
+
+        loop {
+            interval.tick().await;
+
+            let osu_match = match self.osu_client.osu_match(self.match_id).after(cursor).await {
+                Ok(osu_match) => {
+                    failures = 0;
+                    osu_match
+                }
+                Err(why) => {
+                    error!("Failed to poll match {}: {}", self.match_id, why);
+
+                    failures += 1;
+                    if matches!(why, OsuError::NotFound) || failures >= MAX_POLL_FAILURES {
+                        let builder = CreateMessage::new().content(format!(
+                            "Couldn't get match **{match_name}** from osu!, stopped tracking."
+                        ));
+                        self.channel_id.send_message(&self.http, builder).await?;
+                        return Ok(());
+                    }
+                    continue;
+                }
+            };
+
+            for game in finished_games(&osu_match) {
+                if !self.posted_games.insert(game.game_id) {
+                    continue;
+                }
+
+                self.add_team_win(game);
+
+                if let Err(why) = self.notify_game(game, &osu_match).await {
+                    error!("Failed to post game in match {}: {}", self.match_id, why);
+                }
+            }
+
+            if osu_match.match_info.end_time.is_some()
+                || osu_match
+                    .events
+                    .iter()
+                    .any(|event| matches!(event, MatchEvent::Disbanded { .. }))
+            {
+                let builder = CreateMessage::new().content(format!(
+                    "Match **{}** has closed, stopped tracking.",
+                    osu_match.match_info.name
+                ));
+                self.channel_id.send_message(&self.http, builder).await?;
+                return Ok(());
+            }
+
+            cursor = next_cursor(&osu_match).max(cursor);
+        }
+    }
+
+    fn add_team_win(&mut self, game: &MatchGame) {
+        if !matches!(game.team_type, TeamType::TeamVS | TeamType::TagTeamVS) {
+            return;
+        }
+
+        let (red, blue) = team_totals(&game.scores);
+        match red.cmp(&blue) {
+            std::cmp::Ordering::Greater => self.red_wins += 1,
+            std::cmp::Ordering::Less => self.blue_wins += 1,
+            std::cmp::Ordering::Equal => {}
+        }
+    }
+
+    async fn notify_game(&self, game: &MatchGame, osu_match: &OsuMatch) -> Result<(), Error> {
+        let connection = &mut self.pool.get().await?;
+        let beatmap = get_beatmap(connection, self.osu_client.clone(), game.map_id).await?;
+
+        let mut scores = game.scores.iter().collect::<Vec<&MatchScore>>();
+        scores.sort_by(|a, b| b.score.cmp(&a.score));
+
+        let mut lines = Vec::new();
+        for (position, score) in scores.iter().enumerate() {
+            let username = osu_match.users.get(&score.user_id).map_or_else(
+                || score.user_id.to_string(),
+                |user| user.username.to_string(),
+            );
+
+            let pp = match calculate_match_score(game, score, &beatmap.2) {
+                Ok(pp) => format!("{}pp", remove_trailing_zeros(pp, 2)?),
+                Err(_) => String::from("-"),
+            };
+
+            lines.push(format!(
+                "{}. {}**{}** {} • {}% • {}x • {}{}",
+                position + 1,
+                team_marker(score.team),
+                username,
+                score.score.to_formatted_string(&Locale::en),
+                remove_trailing_zeros(f64::from(score.accuracy), 2)?,
+                score.max_combo,
+                pp,
+                if score.pass { "" } else { " (Failed)" }
+            ));
+        }
+
+        if matches!(game.team_type, TeamType::TeamVS | TeamType::TagTeamVS) {
+            let (red, blue) = team_totals(&game.scores);
+            lines.push(format!(
+                "\n🔴 {} | {} 🔵\n**Running score: Red {} - {} Blue**",
+                red.to_formatted_string(&Locale::en),
+                blue.to_formatted_string(&Locale::en),
+                self.red_wins,
+                self.blue_wins
+            ));
+        }
+
+        let embed = create_game_embed(
+            &beatmap,
+            game,
+            &lines.join("\n"),
+            &osu_match.match_info.name,
+        );
+
+        let builder = CreateMessage::new().embed(embed);
+        self.channel_id.send_message(&self.http, builder).await?;
+
+        Ok(())
+    }
+}
+
+fn create_game_embed<'a>(
+    beatmap: &'a (Beatmap, Beatmapset, OsuFile),
+    game: &MatchGame,
+    description: &'a str,
+    match_name: &'a str,
+) -> CreateEmbed<'a> {
+    let mods = game.mods.to_string();
+
+    CreateEmbed::new()
+        .title(format!(
+            "{} - {} [{}] +{}",
+            beatmap.1.artist,
+            beatmap.1.title,
+            beatmap.0.version,
+            if mods.is_empty() { "NM" } else { &mods }
+        ))
+        .url(format_beatmap_link(
+            Some(beatmap.0.id),
+            beatmap.1.id,
+            Some(&game.mode.to_string()),
+        ))
+        .thumbnail(
+            beatmap.1.list_cover.as_str(),
+            Some("The osu map's background".into()),
+        )
+        .color(BLUE)
+        .description(description)
+        .footer(CreateEmbedFooter::new(match_name))
+}
+
+pub fn finished_games(osu_match: &OsuMatch) -> impl Iterator<Item = &MatchGame> {
+    osu_match.events.iter().filter_map(|event| match event {
+        MatchEvent::Game { game, .. } if game.end_time.is_some() => Some(game.as_ref()),
+        _ => None,
+    })
+}
+
+/// The event id to poll after. Stays behind unfinished games so that their results are
+/// fetched again once they end.
+fn next_cursor(osu_match: &OsuMatch) -> u64 {
+    osu_match
+        .events
+        .iter()
+        .filter_map(|event| match event {
+            MatchEvent::Game { id, game, .. } if game.end_time.is_none() => Some(id - 1),
+            _ => None,
+        })
+        .min()
+        .unwrap_or(osu_match.latest_event_id)
+}
+
+fn team_marker(team: Team) -> &'static str {
+    match team {
+        Team::Red => "🔴 ",
+        Team::Blue => "🔵 ",
+        Team::None => "",
+    }
+}
+
+pub fn team_totals(scores: &[MatchScore]) -> (u64, u64) {
+    scores
+        .iter()
+        .fold((0, 0), |(red, blue), score| match score.team {
+            Team::Red => (red + u64::from(score.score), blue),
+            Team::Blue => (red, blue + u64::from(score.score)),
+            Team::None => (red, blue),
+        })
+}
+
+pub fn calculate_match_score(
+    game: &MatchGame,
+    score: &MatchScore,
+    osu_file: &OsuFile,
+) -> Result<f64, Error> {
+    let mut mods = game.mods.clone();
+    mods.extend(score.mods.iter());
+
+    let simulated = SimulatedScore {
+        mods: mods.with_mode(game.mode).into(),
+        acc: Some(f64::from(score.accuracy)),
+        n300: Some(score.statistics.count_300),
+        n100: Some(score.statistics.count_100),
+        n50: Some(score.statistics.count_50),
+        nmiss: Some(score.statistics.count_miss),
+        combo: Some(score.max_combo),
+    };
+
+    Ok(calculate_simulated(osu_file, game.mode, &simulated)?.pp)
+}
+
+pub struct MatchCost {
+    pub user_id: u32,
+    pub team: Team,
+    pub cost: f64,
+    pub maps_played: usize,
+}
+
+/// Fetches every event of a match, following the pagination back to the creation of the lobby.
+pub async fn get_full_match(
+    osu_client: &Osu,
+    match_id: u32,
+) -> Result<(OsuMatch, HashMap<u32, User>), Error> {
+    let mut osu_match = osu_client.osu_match(match_id).await?;
+    let mut users = osu_match.users.drain().collect::<HashMap<u32, User>>();
+
+    while let Some(first_event) = osu_match.events.first()
+        && first_event.event_id() > osu_match.first_event_id
+    {
+        let mut previous = osu_client
+            .osu_match(match_id)
+            .before(first_event.event_id())
+            .await?;
+
+        if previous.events.is_empty() {
+            break;
+        }
+
+        users.extend(previous.users.drain());
+        previous.events.append(&mut osu_match.events);
+        osu_match.events = previous.events;
+    }
+
+    Ok((osu_match, users))
+}
+
+fn median(values: &mut [f64]) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+
+    values.sort_by(f64::total_cmp);
+    let middle = values.len() / 2;
+    if values.len() % 2 == 0 {
+        Some((values[middle - 1] + values[middle]) / 2.0)
+    } else {
+        Some(values[middle])
+    }
+}
+
+/// A player's match cost is their average score relative to the median score of each map they
+/// played, multiplied by the cube root of how many maps they played compared to the average
+/// player, so that subs who only played their best maps don't come out on top.
+pub fn calculate_match_costs(games: &[&MatchGame]) -> Vec<MatchCost> {
+    let mut relative_scores: HashMap<u32, (Team, Vec<f64>)> = HashMap::new();
+
+    for game in games {
+        let scores = game
+            .scores
+            .iter()
+            .filter(|score| score.score > 0)
+            .collect::<Vec<&MatchScore>>();
+
+        let Some(map_median) = median(
+            &mut scores
+                .iter()
+                .map(|score| f64::from(score.score))
+                .collect::<Vec<f64>>(),
+        ) else {
+            continue;
+        };
+
+        for score in scores {
+            let entry = relative_scores
+                .entry(score.user_id)
+                .or_insert_with(|| (score.team, Vec::new()));
+            entry.0 = score.team;
+            entry.1.push(f64::from(score.score) / map_median);
+        }
+    }
+
+    if relative_scores.is_empty() {
+        return Vec::new();
+    }
+
+    let average_played = relative_scores
+        .values()
+        .map(|(_, scores)| scores.len() as f64)
+        .sum::<f64>()
+        / relative_scores.len() as f64;
+
+    let mut match_costs = relative_scores
+        .into_iter()
+        .map(|(user_id, (team, scores))| {
+            let maps_played = scores.len();
+            let average = scores.iter().sum::<f64>() / maps_played as f64;
+            MatchCost {
+                user_id,
+                team,
+                cost: average * (maps_played as f64 / average_played).cbrt(),
+                maps_played,
+            }
+        })
+        .collect::<Vec<MatchCost>>();
+
+    match_costs.sort_by(|a, b| b.cost.total_cmp(&a.cost));
+    match_costs
+}
+
+pub fn format_match_costs(
+    match_costs: &[MatchCost],
+    users: &HashMap<u32, User>,
+) -> Result<Vec<String>, Error> {
+    let mut formatted = Vec::new();
+    for (position, match_cost) in match_costs.iter().enumerate() {
+        let username = users.get(&match_cost.user_id).map_or_else(
+            || match_cost.user_id.to_string(),
+            |user| user.username.to_string(),
+        );
+
+        formatted.push(format!(
+            "{}. {}[{}]({}) - **{}** ({} {})",
+            position + 1,
+            team_marker(match_cost.team),
+            username,
+            format_user_link(i64::from(match_cost.user_id)),
+            remove_trailing_zeros(match_cost.cost, 2)?,
+            match_cost.maps_played,
+            if match_cost.maps_played == 1 {
+                "map"
+            } else {
+                "maps"
+            }
+        ));
+    }
+
+    Ok(formatted)
+}
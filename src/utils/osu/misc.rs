@@ -5,6 +5,7 @@ use crate::models::osu_files::OsuFile;
 use crate::models::osu_users::{NewOsuUser, OsuUser};
 use crate::plugins::osu::{GameModeChoices, SortChoices};
 use crate::utils::db::{linked_osu_profiles, osu_notifications, osu_users};
+use crate::utils::misc::get_reply;
 use crate::utils::osu::caching::get_beatmap;
 use crate::utils::osu::calculate;
 use crate::utils::osu::pp::CalculateResults;
@@ -12,7 +13,9 @@ use crate::utils::osu::regex::{BeatmapInfo, get_beatmap_info};
 use diesel_async::AsyncPgConnection;
 use par_stream::ParStreamExt;
 use poise::futures_util::StreamExt;
-use poise::serenity_prelude::{Cache, GuildId, Message, Presence, User, UserId};
+use poise::serenity_prelude::{
+    Cache, GenericChannelId, GetMessages, GuildId, Http, Message, Presence, User, UserId,
+};
 use rosu_v2::model::GameMode;
 use rosu_v2::prelude::{Score, ScoreStatistics, UserExtended};
 use serde::{Deserialize, Serialize};
@@ -87,6 +90,42 @@ pub fn calculate_potential_acc(score: &Score) -> Option<f64> {
     }
 }
 
+pub fn weighted_pp(top_pp: &[f64]) -> f64 {
+    top_pp
+        .iter()
+        .take(100)
+        .zip(0..)
+        .map(|(pp, index)| pp * 0.95_f64.powi(index))
+        .sum()
+}
+
+/// Calculates the raw pp a single new play needs to reach `target` total pp, and the
+/// index it would take in the (descending) top list. Returns None if the target
+/// can't be reached with a single play.
+pub fn calculate_pp_needed(top_pp: &[f64], total_pp: f64, target: f64) -> Option<(f64, usize)> {
+    let bonus_pp = (total_pp - weighted_pp(top_pp)).max(0.0);
+    let weighted_target = target - bonus_pp;
+    let top_pp = &top_pp[..top_pp.len().min(100)];
+
+    let mut prefix = 0.0;
+    for position in 0..=top_pp.len().min(99) {
+        let suffix = top_pp[position..top_pp.len().min(99)]
+            .iter()
+            .zip(0..)
+            .map(|(pp, index)| pp * 0.95_f64.powi(position as i32 + 1 + index))
+            .sum::<f64>();
+        let needed = (weighted_target - prefix - suffix) / 0.95_f64.powi(position as i32);
+
+        if top_pp.get(position).is_none_or(|pp| needed >= *pp) {
+            return Some((needed, position));
+        }
+
+        prefix += top_pp[position] * 0.95_f64.powi(position as i32);
+    }
+
+    None
+}
+
 pub fn is_perfect(statistics: &ScoreStatistics) -> bool {
     if statistics.miss > 0 || statistics.large_tick_miss > 0 || statistics.combo_break > 0 {
         return false;
@@ -99,9 +138,7 @@ pub fn count_score_pages(score_count: usize, scores_per_page: usize) -> usize {
 }
 
 pub async fn wipe_profile_data(db: &mut AsyncPgConnection, user_id: i64) -> Result<(), Error> {
-    if osu_users::read(db, user_id).await.is_ok() {
-        osu_users::delete(db, user_id).await?;
-    }
+    osu_users::delete(db, user_id).await?;
 
     if osu_notifications::read(db, user_id).await.is_ok() {
         osu_notifications::delete(db, user_id).await?;
@@ -260,6 +297,29 @@ pub async fn get_user_by_username(
     }
 }
 
+/// Splits an `--alt` flag off of a user argument. The flag can be followed by the position of
+/// the alt account to use, and selects the first alt otherwise.
+pub fn parse_alt_flag(user: &str) -> (String, Option<usize>) {
+    let mut alt = None;
+    let mut words = Vec::new();
+    let mut parts = user.split_whitespace().peekable();
+
+    while let Some(part) = parts.next() {
+        if part == "--alt" {
+            alt = Some(
+                parts
+                    .next_if(|next| next.parse::<usize>().is_ok_and(|position| position > 0))
+                    .and_then(|position| position.parse::<usize>().ok())
+                    .unwrap_or(1),
+            );
+        } else {
+            words.push(part);
+        }
+    }
+
+    (words.join(" "), alt)
+}
+
 pub async fn get_user(
     ctx: crate::Context<'_>,
     discord_user: &User,
@@ -267,11 +327,37 @@ pub async fn get_user(
     connection: &mut AsyncPgConnection,
     mode: Option<GameModeChoices>,
 ) -> Result<Option<UserExtended>, Error> {
+    let (user, alt) = match user.as_deref().map(parse_alt_flag) {
+        Some((user, alt)) if user.is_empty() => (None, alt),
+        Some((user, alt)) => (Some(user), alt),
+        None => (None, None),
+    };
+
     if let Some(user) = user {
         Ok(get_user_by_username(ctx, &user, mode).await?)
     } else {
-        let linked_profile =
-            linked_osu_profiles::read(connection, i64::try_from(discord_user.id.get())?).await;
+        let discord_id = i64::try_from(discord_user.id.get())?;
+        let linked_profile = if let Some(position) = alt {
+            let alt_profile = linked_osu_profiles::get_accounts(connection, discord_id)
+                .await?
+                .into_iter()
+                .filter(|profile| !profile.is_primary)
+                .nth(position - 1);
+
+            let Some(alt_profile) = alt_profile else {
+                ctx.say(format!(
+                    "**{}** doesn't have an alt account #{position} linked.",
+                    discord_user.name
+                ))
+                .await?;
+                return Ok(None);
+            };
+
+            Ok(alt_profile)
+        } else {
+            linked_osu_profiles::read(connection, discord_id).await
+        };
+
         if let Ok(linked_profile) = linked_profile {
             let mode: GameMode = if let Some(mode) = mode {
                 mode.into()
@@ -357,6 +443,54 @@ pub async fn find_beatmap_link(messages: Vec<Message>) -> Result<Option<BeatmapI
     Ok(None)
 }
 
+/// Finds the latest beatmap link posted in a channel.
+pub async fn find_recent_beatmap_link(
+    http: &Http,
+    channel_id: GenericChannelId,
+) -> Result<Option<BeatmapInfo>, Error> {
+    find_beatmap_link(
+        channel_id
+            .messages(http, GetMessages::new().limit(100))
+            .await?,
+    )
+    .await
+}
+
+/// Finds the beatmap a command is about: the linked one, the one in the message being replied
+/// to, or the latest one posted in the channel. Tells the user and returns `None` when there isn't
+/// one.
+pub async fn get_command_beatmap(
+    ctx: crate::Context<'_>,
+    beatmap_url: Option<url::Url>,
+    require_difficulty: bool,
+) -> Result<Option<BeatmapInfo>, Error> {
+    if let Some(beatmap_url) = beatmap_url {
+        let beatmap_info = get_beatmap_info(beatmap_url.as_str())?;
+        if require_difficulty && beatmap_info.beatmap_id.is_none() {
+            ctx.say("Please link to a specific beatmap difficulty.")
+                .await?;
+            return Ok(None);
+        } else if !require_difficulty && beatmap_info.beatmapset_id.is_none() {
+            ctx.say("Please link to a beatmapset.").await?;
+            return Ok(None);
+        }
+        return Ok(Some(beatmap_info));
+    }
+
+    if let Some(reply) = get_reply(ctx)
+        && let Some(beatmap_info) = find_beatmap_link(vec![reply]).await?
+    {
+        return Ok(Some(beatmap_info));
+    }
+
+    let beatmap_info = find_recent_beatmap_link(ctx.http(), ctx.channel_id()).await?;
+    if beatmap_info.is_none() {
+        ctx.say("No beatmap link found.").await?;
+    }
+
+    Ok(beatmap_info)
+}
+
 pub fn get_score_position(score: &Score, mut score_list: Vec<Score>) -> Result<usize, Error> {
     let mut found_index = None;
     for (i, list_score) in score_list.iter().enumerate() {
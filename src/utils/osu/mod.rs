@@ -2,11 +2,19 @@ pub mod caching;
 pub mod calculate;
 pub mod card;
 pub mod embeds;
+pub mod graphs;
+pub mod leaderboard;
+pub mod map_feed;
 pub mod map_format;
+pub mod matches;
 pub mod misc;
 pub mod misc_format;
+pub mod playfield;
 pub mod pp;
+pub mod recommend;
 pub mod regex;
+pub mod replay;
 pub mod score_format;
 pub mod scores_ws;
 pub mod tracking;
+pub mod verification;
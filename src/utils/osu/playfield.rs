@@ -0,0 +1,562 @@
+use crate::models::beatmaps::Beatmap;
+use crate::models::beatmapsets::Beatmapset;
+use crate::models::osu_files::OsuFile;
+use crate::utils::db::osu_guild_channels;
+use crate::utils::osu::caching::get_beatmap;
+use crate::utils::osu::graphs::playfield::render_playfield;
+use crate::utils::osu::misc::{find_recent_beatmap_link, gamemode_from_string};
+use crate::utils::osu::misc_format::format_beatmap_link;
+use crate::utils::osu::regex::get_editor_timestamp;
+use crate::{Data, Error};
+use poise::serenity_prelude::model::colour::colours::roles::BLUE;
+use poise::serenity_prelude::{CreateAttachment, CreateEmbed, CreateMessage, Message};
+use rosu_pp::model::hit_object::{HitObjectKind, PathControlPoint, SplineType};
+use rosu_v2::model::GameMode;
+use std::f64::consts::TAU;
+
+// Combo colours osu! falls back to when a beatmap doesn't define its own.
+const DEFAULT_COMBO_COLORS: [(u8, u8, u8); 4] =
+    [(255, 192, 0), (0, 202, 0), (18, 124, 255), (242, 24, 57)];
+// How many points every curved piece of a slider gets sampled into.
+const CURVE_SAMPLES: usize = 50;
+
+pub enum ObjectKind {
+    Circle,
+    Slider {
+        /// Sampled slider path in osu! pixels, already cut to the length of the slider.
+        path: Vec<(f64, f64)>,
+        repeats: u32,
+    },
+    Spinner,
+}
+
+pub struct PlayfieldObject {
+    pub x: f64,
+    pub y: f64,
+    pub start_time: f64,
+    pub end_time: f64,
+    pub new_combo: bool,
+    pub combo_number: u32,
+    pub combo_color: (u8, u8, u8),
+    pub kind: ObjectKind,
+}
+
+impl PlayfieldObject {
+    /// Where the object ends, which is where follow points to the next object start from.
+    pub fn end_position(&self) -> (f64, f64) {
+        match &self.kind {
+            ObjectKind::Slider { path, repeats } if repeats % 2 == 1 => {
+                path.last().copied().unwrap_or((self.x, self.y))
+            }
+            _ => (self.x, self.y),
+        }
+    }
+
+    /// Position of the slider ball at `time`, or the position of the object otherwise.
+    pub fn position_at(&self, time: f64) -> (f64, f64) {
+        let ObjectKind::Slider { path, repeats } = &self.kind else {
+            return (self.x, self.y);
+        };
+
+        let duration = self.end_time - self.start_time;
+        if duration <= 0.0 {
+            return (self.x, self.y);
+        }
+
+        let progress = ((time - self.start_time) / duration).clamp(0.0, 1.0) * f64::from(*repeats);
+        let span = progress.floor().min(f64::from(*repeats) - 1.0);
+        let span_progress = progress - span;
+        // Every other span runs the slider backwards.
+        if span as u32 % 2 == 1 {
+            point_along(path, 1.0 - span_progress)
+        } else {
+            point_along(path, span_progress)
+        }
+    }
+}
+
+pub struct Playfield {
+    pub circle_size: f64,
+    pub approach_rate: f64,
+    pub objects: Vec<PlayfieldObject>,
+}
+
+impl Playfield {
+    pub fn radius(&self) -> f64 {
+        54.4 - 4.48 * self.circle_size
+    }
+
+    /// How long before its start time an object appears.
+    pub fn preempt(&self) -> f64 {
+        if self.approach_rate < 5.0 {
+            1200.0 + 600.0 * (5.0 - self.approach_rate) / 5.0
+        } else {
+            1200.0 - 750.0 * (self.approach_rate - 5.0) / 5.0
+        }
+    }
+
+    pub fn fade_in(&self) -> f64 {
+        400.0 * (self.preempt() / 450.0).min(1.0)
+    }
+
+    pub fn is_visible(&self, object: &PlayfieldObject, time: f64) -> bool {
+        object.start_time - self.preempt() <= time && time <= object.end_time
+    }
+}
+
+/// Reads the hit objects out of a cached .osu file. Only osu!standard beatmaps are supported,
+/// since other modes don't have a playfield to draw.
+pub fn parse_playfield(file: &[u8]) -> Result<Playfield, Error> {
+    let map = rosu_pp::Beatmap::from_bytes(file)?;
+
+    if map.mode != rosu_pp::model::mode::GameMode::Osu {
+        return Err(Error::from(
+            "Only osu!standard beatmaps can be drawn on a playfield",
+        ));
+    }
+
+    let (mut combo_colors, combo_flags) = parse_combo_info(file);
+    if combo_colors.is_empty() {
+        combo_colors = DEFAULT_COMBO_COLORS.to_vec();
+    }
+
+    let mut objects: Vec<PlayfieldObject> = Vec::new();
+    let mut combo_number = 0;
+    let mut color_index = 0;
+    let mut next_flag = 0;
+    for hit_object in &map.hit_objects {
+        let x = f64::from(hit_object.pos.x);
+        let y = f64::from(hit_object.pos.y);
+        let start_time = hit_object.start_time;
+
+        // rosu-pp sorts the objects by time and drops lines it can't parse, so the flags are
+        // matched up by time rather than by position. Flags without an object are skipped.
+        while combo_flags
+            .get(next_flag)
+            .is_some_and(|(time, _)| *time < start_time - 0.5)
+        {
+            next_flag += 1;
+        }
+        let object_type = match combo_flags.get(next_flag) {
+            Some((time, object_type)) if (time - start_time).abs() <= 0.5 => {
+                next_flag += 1;
+                *object_type
+            }
+            _ => 0,
+        };
+
+        // Objects after a spinner always start a new combo, even without the flag set.
+        let new_combo = objects.last().is_none_or(|previous| {
+            object_type & 4 != 0 || matches!(previous.kind, ObjectKind::Spinner)
+        });
+        if new_combo {
+            if !objects.is_empty() {
+                color_index += 1 + ((object_type >> 4) & 7) as usize;
+            }
+            combo_number = 1;
+        } else {
+            combo_number += 1;
+        }
+
+        let (kind, end_time) = match &hit_object.kind {
+            HitObjectKind::Slider(slider) => {
+                let repeats = u32::try_from(slider.repeats + 1)?;
+                let length = slider.expected_dist.unwrap_or(0.0);
+                let path = slider_path(x, y, &slider.control_points, length);
+                let duration = slider_duration(&map, start_time, length, repeats);
+                (ObjectKind::Slider { path, repeats }, start_time + duration)
+            }
+            HitObjectKind::Spinner(spinner) => (ObjectKind::Spinner, start_time + spinner.duration),
+            _ => (ObjectKind::Circle, start_time),
+        };
+
+        objects.push(PlayfieldObject {
+            x,
+            y,
+            start_time,
+            end_time,
+            new_combo,
+            combo_number,
+            combo_color: combo_colors[color_index % combo_colors.len()],
+            kind,
+        });
+    }
+
+    Ok(Playfield {
+        circle_size: f64::from(map.cs),
+        approach_rate: f64::from(map.ar),
+        objects,
+    })
+}
+
+/// rosu-pp doesn't keep combo colours or new combo flags, so they're read from the file. Returns
+/// the combo colours and the start time and type of every hit object, sorted by time.
+fn parse_combo_info(file: &[u8]) -> (Vec<(u8, u8, u8)>, Vec<(f64, u32)>) {
+    let content = String::from_utf8_lossy(file);
+
+    let mut section = "";
+    let mut time_offset = 0.0;
+    let mut combo_colors = Vec::new();
+    let mut object_types = Vec::new();
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with("//") {
+            continue;
+        }
+
+        // Old beatmaps are played 24ms later than their timestamps say, which rosu-pp applies to
+        // the objects as well.
+        if let Some(version) = line.strip_prefix("osu file format v") {
+            if version
+                .trim()
+                .parse::<u32>()
+                .is_ok_and(|version| version < 5)
+            {
+                time_offset = 24.0;
+            }
+            continue;
+        }
+
+        if let Some(name) = line
+            .strip_prefix('[')
+            .and_then(|line| line.strip_suffix(']'))
+        {
+            section = name;
+            continue;
+        }
+
+        match section {
+            "Colours" => {
+                let Some((key, value)) = line.split_once(':') else {
+                    continue;
+                };
+                if !key.trim().starts_with("Combo") {
+                    continue;
+                }
+                let rgb = value
+                    .split(',')
+                    .filter_map(|channel| channel.trim().parse::<u8>().ok())
+                    .collect::<Vec<u8>>();
+                if let [r, g, b, ..] = rgb[..] {
+                    combo_colors.push((r, g, b));
+                }
+            }
+            "HitObjects" => {
+                let mut fields = line.split(',').skip(2);
+                let time = fields
+                    .next()
+                    .and_then(|time| time.trim().parse::<f64>().ok());
+                let object_type = fields
+                    .next()
+                    .and_then(|object_type| object_type.trim().parse::<u32>().ok());
+                if let (Some(time), Some(object_type)) = (time, object_type) {
+                    object_types.push((time + time_offset, object_type));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // Stable, like rosu-pp's sort, so objects at the same time stay in file order.
+    object_types.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    (combo_colors, object_types)
+}
+
+fn slider_duration(map: &rosu_pp::Beatmap, time: f64, length: f64, repeats: u32) -> f64 {
+    // The points in effect are the last ones before the slider, or the first ones if there are
+    // none before it.
+    let beat_length = map
+        .timing_points
+        .iter()
+        .take_while(|point| point.time <= time)
+        .last()
+        .or_else(|| map.timing_points.first())
+        .map_or(500.0, |point| point.beat_len);
+    let velocity = map
+        .difficulty_points
+        .iter()
+        .take_while(|point| point.time <= time)
+        .last()
+        .map_or(1.0, |point| point.slider_velocity);
+
+    length / (map.slider_multiplier * 100.0 * velocity) * beat_length * f64::from(repeats)
+}
+
+/// Turns the control points of a slider into a list of points. Every control point with a path
+/// type starts a new segment, which ends on the next one.
+fn slider_path(
+    x: f64,
+    y: f64,
+    control_points: &[PathControlPoint],
+    length: f64,
+) -> Vec<(f64, f64)> {
+    let points = control_points
+        .iter()
+        .map(|point| (x + f64::from(point.pos.x), y + f64::from(point.pos.y)))
+        .collect::<Vec<(f64, f64)>>();
+
+    let Some(last) = points.len().checked_sub(1) else {
+        return vec![(x, y)];
+    };
+
+    let mut segment_starts = (1..last)
+        .filter(|index| control_points[*index].path_type.is_some())
+        .collect::<Vec<usize>>();
+    segment_starts.insert(0, 0);
+
+    let mut path = Vec::new();
+    for (position, start) in segment_starts.iter().enumerate() {
+        let end = segment_starts.get(position + 1).copied().unwrap_or(last);
+        let segment = &points[*start..=end];
+
+        let spline_type = control_points[*start]
+            .path_type
+            .as_ref()
+            .map(|path_type| path_type.kind);
+        path.extend(match spline_type {
+            Some(SplineType::PerfectCurve) if segment.len() == 3 => {
+                perfect_curve(segment).unwrap_or_else(|| bezier_curve(segment))
+            }
+            // Catmull sliders only show up in very old beatmaps, so straight lines are close enough.
+            Some(SplineType::Linear | SplineType::Catmull) => segment.to_vec(),
+            _ => bezier_curve(segment),
+        });
+    }
+
+    path.dedup();
+    cut_path(&path, length)
+}
+
+fn bezier_curve(points: &[(f64, f64)]) -> Vec<(f64, f64)> {
+    if points.len() < 3 {
+        return points.to_vec();
+    }
+
+    (0..=CURVE_SAMPLES)
+        .filter_map(|sample| {
+            let t = sample as f64 / CURVE_SAMPLES as f64;
+            let mut points = points.to_vec();
+            while points.len() > 1 {
+                points = points
+                    .windows(2)
+                    .map(|pair| {
+                        (
+                            pair[0].0 + (pair[1].0 - pair[0].0) * t,
+                            pair[0].1 + (pair[1].1 - pair[0].1) * t,
+                        )
+                    })
+                    .collect();
+            }
+            points.first().copied()
+        })
+        .collect()
+}
+
+/// Samples the arc going through all three points, or returns `None` if they're on a line.
+fn perfect_curve(points: &[(f64, f64)]) -> Option<Vec<(f64, f64)>> {
+    let [(ax, ay), (bx, by), (cx, cy)] = points[..] else {
+        return None;
+    };
+
+    let d = 2.0 * (ax * (by - cy) + bx * (cy - ay) + cx * (ay - by));
+    if d.abs() < f64::EPSILON {
+        return None;
+    }
+
+    let a_squared = ax * ax + ay * ay;
+    let b_squared = bx * bx + by * by;
+    let c_squared = cx * cx + cy * cy;
+    let center_x = (a_squared * (by - cy) + b_squared * (cy - ay) + c_squared * (ay - by)) / d;
+    let center_y = (a_squared * (cx - bx) + b_squared * (ax - cx) + c_squared * (bx - ax)) / d;
+    let radius = (ax - center_x).hypot(ay - center_y);
+
+    let start_angle = (ay - center_y).atan2(ax - center_x);
+    let mut end_angle = (cy - center_y).atan2(cx - center_x);
+
+    // The arc has to go through the middle point, which decides the direction it runs in.
+    if (bx - ax) * (cy - ay) - (by - ay) * (cx - ax) > 0.0 {
+        while end_angle < start_angle {
+            end_angle += TAU;
+        }
+    } else {
+        while end_angle > start_angle {
+            end_angle -= TAU;
+        }
+    }
+
+    Some(
+        (0..=CURVE_SAMPLES)
+            .map(|sample| {
+                let angle =
+                    start_angle + (end_angle - start_angle) * sample as f64 / CURVE_SAMPLES as f64;
+                (
+                    center_x + radius * angle.cos(),
+                    center_y + radius * angle.sin(),
+                )
+            })
+            .collect(),
+    )
+}
+
+/// Cuts the path off after `length` osu! pixels, which is where the slider actually ends.
+fn cut_path(path: &[(f64, f64)], length: f64) -> Vec<(f64, f64)> {
+    if length <= 0.0 {
+        return path.to_vec();
+    }
+
+    let mut cut = Vec::new();
+    let mut travelled = 0.0;
+    for pair in path.windows(2) {
+        let (from, to) = (pair[0], pair[1]);
+        if cut.is_empty() {
+            cut.push(from);
+        }
+
+        let distance = (to.0 - from.0).hypot(to.1 - from.1);
+        if travelled + distance >= length {
+            let t = if distance > 0.0 {
+                (length - travelled) / distance
+            } else {
+                0.0
+            };
+            cut.push((from.0 + (to.0 - from.0) * t, from.1 + (to.1 - from.1) * t));
+            return cut;
+        }
+
+        travelled += distance;
+        cut.push(to);
+    }
+
+    if cut.is_empty() { path.to_vec() } else { cut }
+}
+
+fn point_along(path: &[(f64, f64)], fraction: f64) -> (f64, f64) {
+    let total = path
+        .windows(2)
+        .map(|pair| (pair[1].0 - pair[0].0).hypot(pair[1].1 - pair[0].1))
+        .sum::<f64>();
+
+    let mut remaining = total * fraction;
+    for pair in path.windows(2) {
+        let distance = (pair[1].0 - pair[0].0).hypot(pair[1].1 - pair[0].1);
+        if remaining <= distance && distance > 0.0 {
+            let t = remaining / distance;
+            return (
+                pair[0].0 + (pair[1].0 - pair[0].0) * t,
+                pair[0].1 + (pair[1].1 - pair[0].1) * t,
+            );
+        }
+        remaining -= distance;
+    }
+
+    path.last().copied().unwrap_or_default()
+}
+
+/// Formats milliseconds the same way the osu! editor does, e.g. `01:23:456`.
+pub fn format_editor_time(milliseconds: f64) -> String {
+    let milliseconds = milliseconds.max(0.0).round() as i64;
+    format!(
+        "{:02}:{:02}:{:03}",
+        milliseconds / 60000,
+        milliseconds / 1000 % 60,
+        milliseconds % 1000
+    )
+}
+
+/// Draws the playfield of a beatmap at `time` and builds the embed it gets sent in.
+pub fn create_snapshot(
+    beatmap: &(Beatmap, Beatmapset, OsuFile),
+    time: f64,
+) -> Result<(CreateEmbed<'static>, Vec<u8>), Error> {
+    let playfield = parse_playfield(&beatmap.2.file)?;
+    let title = format!(
+        "{} [{}]  •  {}",
+        beatmap.1.title,
+        beatmap.0.version,
+        format_editor_time(time)
+    );
+    let snapshot = render_playfield(&title, &playfield, time)?.encode_png()?;
+
+    let embed = CreateEmbed::new()
+        .title(format!(
+            "{} - {} [{}]",
+            beatmap.1.artist, beatmap.1.title, beatmap.0.version
+        ))
+        .url(format_beatmap_link(
+            Some(beatmap.0.id),
+            beatmap.1.id,
+            Some(&beatmap.0.mode),
+        ))
+        .image("attachment://playfield.png")
+        .color(BLUE);
+
+    Ok((embed, snapshot))
+}
+
+/// Replies to editor timestamps posted in channels that opted into it, using the last beatmap
+/// linked in the channel.
+pub async fn respond_to_timestamp(
+    ctx: &poise::serenity_prelude::Context,
+    message: &Message,
+    data: &Data,
+) -> Result<(), Error> {
+    let Some(guild_id) = message.guild_id else {
+        return Ok(());
+    };
+    if message.author.bot() {
+        return Ok(());
+    }
+
+    // Checked before touching the database, since most messages won't have a timestamp.
+    let Some(time) = get_editor_timestamp(&message.content) else {
+        return Ok(());
+    };
+
+    let connection = &mut data.db_pool.get().await?;
+    let Ok(guild_config) = osu_guild_channels::read(connection, i64::from(guild_id)).await else {
+        return Ok(());
+    };
+    if !guild_config
+        .timestamp_channel
+        .iter()
+        .flatten()
+        .flatten()
+        .any(|channel_id| *channel_id == i64::from(message.channel_id))
+    {
+        return Ok(());
+    }
+
+    let Some(beatmap_info) = find_recent_beatmap_link(&ctx.http, message.channel_id).await? else {
+        return Ok(());
+    };
+
+    let beatmap = get_beatmap(
+        connection,
+        data.osu_client.clone(),
+        u32::try_from(
+            beatmap_info
+                .beatmap_id
+                .ok_or("Failed to get beatmap ID in respond_to_timestamp")?,
+        )?,
+    )
+    .await?;
+
+    if gamemode_from_string(&beatmap.0.mode) != Some(GameMode::Osu) {
+        return Ok(());
+    }
+
+    let (embed, snapshot) = create_snapshot(&beatmap, time)?;
+    message
+        .channel_id
+        .send_message(
+            &ctx.http,
+            CreateMessage::new()
+                .embed(embed)
+                .add_file(CreateAttachment::bytes(snapshot, "playfield.png")),
+        )
+        .await?;
+
+    Ok(())
+}
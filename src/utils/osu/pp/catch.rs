@@ -1,5 +1,5 @@
 use crate::Error;
-use crate::utils::osu::pp::{CalculateResults, CatchScore};
+use crate::utils::osu::pp::{CalculateResults, CatchScore, get_fc_and_ss_pp};
 use rosu_pp::Beatmap;
 use rosu_pp::catch::CatchPerformance;
 use rosu_pp::model::mode::GameMode;
@@ -13,7 +13,7 @@ pub fn calculate_catch_pp(file: &[u8], score_state: CatchScore) -> Result<Calcul
 
         (
             difficulty,
-            map.attributes().mods(score_state.mods).build(),
+            map.attributes().mods(score_state.mods.clone()).build(),
             None,
         )
     } else {
@@ -27,7 +27,10 @@ pub fn calculate_catch_pp(file: &[u8], score_state: CatchScore) -> Result<Calcul
 
         (
             difficulty,
-            map.attributes().clone().mods(score_state.mods).build(),
+            map.attributes()
+                .clone()
+                .mods(score_state.mods.clone())
+                .build(),
             Some(full_difficulty),
         )
     };
@@ -56,6 +59,10 @@ pub fn calculate_catch_pp(file: &[u8], score_state: CatchScore) -> Result<Calcul
         result = result.tiny_droplet_misses(tiny_droplet_misses);
     }
 
+    if let Some(acc) = score_state.acc {
+        result = result.accuracy(acc);
+    }
+
     let result = result.calculate()?;
 
     let full_calc = if let Some(full_difficulty) = full_difficulty {
@@ -64,11 +71,21 @@ pub fn calculate_catch_pp(file: &[u8], score_state: CatchScore) -> Result<Calcul
         result.clone()
     };
 
+    // Real scores only come with hit counts, so the FC is calculated at their accuracy.
+    let (fc_pp, ss_pp) = get_fc_and_ss_pp(
+        full_calc.clone(),
+        &score_state.mods,
+        score_state.acc.or_else(|| accuracy(&score_state)),
+        None,
+    )?;
+
     Ok(CalculateResults {
         total_stars: full_calc.stars(),
         partial_stars: result.stars(),
         pp: result.pp,
         max_pp: None,
+        fc_pp: Some(fc_pp),
+        ss_pp: Some(ss_pp),
         max_combo: full_calc.max_combo(),
         clock_rate: diff_attributes.clock_rate(),
         ar: Some(diff_attributes.ar()),
@@ -77,3 +94,13 @@ pub fn calculate_catch_pp(file: &[u8], score_state: CatchScore) -> Result<Calcul
         od: Some(diff_attributes.od()),
     })
 }
+
+fn accuracy(score_state: &CatchScore) -> Option<f64> {
+    let caught = score_state.fruits? + score_state.droplets? + score_state.tiny_droplets?;
+    let total = caught + score_state.tiny_droplet_misses? + score_state.nmiss?;
+    if total == 0 {
+        return None;
+    }
+
+    Some(f64::from(caught) / f64::from(total) * 100.0)
+}
@@ -1,5 +1,5 @@
 use crate::Error;
-use crate::utils::osu::pp::{CalculateResults, ManiaScore};
+use crate::utils::osu::pp::{CalculateResults, ManiaScore, get_fc_and_ss_pp};
 use rosu_pp::Beatmap;
 use rosu_pp::mania::ManiaPerformance;
 use rosu_pp::model::mode::GameMode;
@@ -13,7 +13,7 @@ pub fn calculate_mania_pp(file: &[u8], score_state: ManiaScore) -> Result<Calcul
 
         (
             difficulty,
-            map.attributes().mods(score_state.mods).build(),
+            map.attributes().mods(score_state.mods.clone()).build(),
             None,
         )
     } else {
@@ -26,7 +26,7 @@ pub fn calculate_mania_pp(file: &[u8], score_state: ManiaScore) -> Result<Calcul
 
         (
             difficulty,
-            map.attributes().mods(score_state.mods).build(),
+            map.attributes().mods(score_state.mods.clone()).build(),
             Some(full_difficulty),
         )
     };
@@ -55,6 +55,10 @@ pub fn calculate_mania_pp(file: &[u8], score_state: ManiaScore) -> Result<Calcul
         result = result.n50(n50);
     }
 
+    if let Some(acc) = score_state.acc {
+        result = result.accuracy(acc);
+    }
+
     let result = result.calculate()?;
 
     let full_calc = if let Some(full_difficulty) = full_difficulty {
@@ -63,11 +67,21 @@ pub fn calculate_mania_pp(file: &[u8], score_state: ManiaScore) -> Result<Calcul
         result.clone()
     };
 
+    // Real scores only come with hit counts, so the FC is calculated at their accuracy.
+    let (fc_pp, ss_pp) = get_fc_and_ss_pp(
+        full_calc.clone(),
+        &score_state.mods,
+        score_state.acc.or_else(|| accuracy(&score_state)),
+        None,
+    )?;
+
     Ok(CalculateResults {
         total_stars: full_calc.stars(),
         partial_stars: result.stars(),
         pp: result.pp,
         max_pp: None,
+        fc_pp: Some(fc_pp),
+        ss_pp: Some(ss_pp),
         max_combo: full_calc.max_combo(),
         clock_rate: diff_attributes.clock_rate(),
         od: Some(diff_attributes.od()),
@@ -76,3 +90,24 @@ pub fn calculate_mania_pp(file: &[u8], score_state: ManiaScore) -> Result<Calcul
         cs: None,
     })
 }
+
+fn accuracy(score_state: &ManiaScore) -> Option<f64> {
+    let (n320, n300, n200, n100, n50, nmiss) = (
+        score_state.n320?,
+        score_state.n300?,
+        score_state.n200?,
+        score_state.n100?,
+        score_state.n50?,
+        score_state.nmiss?,
+    );
+    let total = n320 + n300 + n200 + n100 + n50 + nmiss;
+    if total == 0 {
+        return None;
+    }
+
+    Some(
+        f64::from(300 * (n320 + n300) + 200 * n200 + 100 * n100 + 50 * n50)
+            / f64::from(300 * total)
+            * 100.0,
+    )
+}
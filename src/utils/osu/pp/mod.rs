@@ -1,4 +1,6 @@
-use rosu_pp::GameMods;
+use crate::Error;
+use rosu_pp::any::IntoPerformance;
+use rosu_pp::{GameMods, Performance};
 
 pub mod catch;
 pub mod mania;
@@ -10,6 +12,8 @@ pub struct CalculateResults {
     pub partial_stars: f64,
     pub pp: f64,
     pub max_pp: Option<f64>,
+    pub fc_pp: Option<f64>,
+    pub ss_pp: Option<f64>,
     pub max_combo: u32,
     pub clock_rate: f64,
     pub od: Option<f32>,
@@ -18,6 +22,27 @@ pub struct CalculateResults {
     pub cs: Option<f32>,
 }
 
+/// Calculates the pp of a full combo at `acc`, and the pp of an SS. Modes that don't have a
+/// lazer toggle leave `lazer` as `None`.
+pub fn get_fc_and_ss_pp<'a>(
+    attributes: impl IntoPerformance<'a> + Clone,
+    mods: &GameMods,
+    acc: Option<f64>,
+    lazer: Option<bool>,
+) -> Result<(f64, f64), Error> {
+    let mut fc_result = Performance::new(attributes.clone()).mods(mods.clone());
+    let mut ss_result = Performance::new(attributes).mods(mods.clone());
+    if let Some(lazer) = lazer {
+        fc_result = fc_result.lazer(lazer);
+        ss_result = ss_result.lazer(lazer);
+    }
+    if let Some(acc) = acc {
+        fc_result = fc_result.accuracy(acc);
+    }
+
+    Ok((fc_result.calculate()?.pp(), ss_result.calculate()?.pp()))
+}
+
 pub struct StandardScore {
     pub mods: GameMods,
     pub passed: bool,
@@ -59,6 +84,7 @@ impl Default for StandardScore {
 pub struct ManiaScore {
     pub mods: GameMods,
     pub passed: bool,
+    pub acc: Option<f64>,
     pub n320: Option<u32>,
     pub n300: Option<u32>,
     pub n200: Option<u32>,
@@ -73,6 +99,7 @@ impl Default for ManiaScore {
         ManiaScore {
             mods: GameMods::default(),
             passed: true,
+            acc: None,
             n320: None,
             n300: None,
             n200: None,
@@ -88,6 +115,7 @@ pub struct CatchScore {
     pub mods: GameMods,
     pub passed: bool,
     pub combo: Option<u32>,
+    pub acc: Option<f64>,
     pub fruits: Option<u32>,
     pub droplets: Option<u32>,
     pub tiny_droplets: Option<u32>,
@@ -102,6 +130,7 @@ impl Default for CatchScore {
             mods: GameMods::default(),
             passed: true,
             combo: None,
+            acc: None,
             fruits: None,
             droplets: None,
             tiny_droplets: None,
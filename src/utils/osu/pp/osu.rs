@@ -1,5 +1,5 @@
 use crate::Error;
-use crate::utils::osu::pp::{CalculateResults, StandardScore};
+use crate::utils::osu::pp::{CalculateResults, StandardScore, get_fc_and_ss_pp};
 use rosu_pp::Beatmap;
 use rosu_pp::model::mode::GameMode;
 use rosu_pp::osu::{OsuPerformance, OsuPerformanceAttributes};
@@ -77,6 +77,13 @@ pub fn calculate_std_pp(
 
     let result = result.calculate()?;
 
+    let (fc_pp, ss_pp) = get_fc_and_ss_pp(
+        full_difficulty.clone().unwrap_or_else(|| result.clone()),
+        &score_state.mods,
+        score_state.acc,
+        Some(score_state.lazer),
+    )?;
+
     let (full_calc, potential_result) = if let Some(full_difficulty) = full_difficulty {
         (
             full_difficulty.clone(),
@@ -94,6 +101,8 @@ pub fn calculate_std_pp(
         partial_stars: result.stars(),
         pp: result.pp,
         max_pp: Some(potential_result?),
+        fc_pp: Some(fc_pp),
+        ss_pp: Some(ss_pp),
         max_combo: full_calc.max_combo(),
         clock_rate: diff_attributes.clock_rate(),
         od: Some(diff_attributes.od()),
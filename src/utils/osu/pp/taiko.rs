@@ -1,5 +1,5 @@
 use crate::Error;
-use crate::utils::osu::pp::{CalculateResults, TaikoScore};
+use crate::utils::osu::pp::{CalculateResults, TaikoScore, get_fc_and_ss_pp};
 use rosu_pp::Beatmap;
 use rosu_pp::model::mode::GameMode;
 use rosu_pp::taiko::TaikoPerformance;
@@ -13,7 +13,7 @@ pub fn calculate_taiko_pp(file: &[u8], score_state: TaikoScore) -> Result<Calcul
 
         (
             difficulty,
-            map.attributes().mods(score_state.mods).build(),
+            map.attributes().mods(score_state.mods.clone()).build(),
             None,
         )
     } else {
@@ -27,7 +27,7 @@ pub fn calculate_taiko_pp(file: &[u8], score_state: TaikoScore) -> Result<Calcul
 
         (
             difficulty,
-            map.attributes().mods(score_state.mods).build(),
+            map.attributes().mods(score_state.mods.clone()).build(),
             Some(full_difficulty),
         )
     };
@@ -60,11 +60,16 @@ pub fn calculate_taiko_pp(file: &[u8], score_state: TaikoScore) -> Result<Calcul
         result.clone()
     };
 
+    let (fc_pp, ss_pp) =
+        get_fc_and_ss_pp(full_calc.clone(), &score_state.mods, score_state.acc, None)?;
+
     Ok(CalculateResults {
         total_stars: full_calc.stars(),
         partial_stars: result.difficulty.stars,
         pp: result.pp,
         max_pp: None,
+        fc_pp: Some(fc_pp),
+        ss_pp: Some(ss_pp),
         max_combo: full_calc.max_combo(),
         clock_rate: diff_attributes.clock_rate(),
         od: Some(diff_attributes.od()),
@@ -0,0 +1,138 @@
+use crate::Error;
+use crate::models::beatmaps::Beatmap;
+use crate::models::beatmapsets::Beatmapset;
+use crate::utils::db::beatmaps::{self, BeatmapSearch};
+use crate::utils::misc::remove_trailing_zeros;
+use crate::utils::osu::pp::CalculateResults;
+use diesel_async::AsyncPgConnection;
+use foldhash::{HashMap, HashMapExt, HashSet};
+use rosu_v2::prelude::Score;
+
+// How many cached maps to consider before removing the ones already in the user's top plays.
+const CANDIDATE_LIMIT: i64 = 500;
+const RECOMMENDATION_LIMIT: usize = 50;
+// Maps where at least this share of the hit objects are sliders count as tech maps.
+const TECH_SLIDER_RATIO: f64 = 0.45;
+
+#[derive(poise::ChoiceParameter, Clone, Copy)]
+pub enum RecommendChoices {
+    #[name = "Farm"]
+    Farm,
+    #[name = "Aim"]
+    #[name = "Improve aim"]
+    Aim,
+    #[name = "Tech"]
+    Tech,
+}
+
+/// A summary of a user's top plays. Stars, BPM and length are the nomod values of the maps
+/// played, so they can be compared directly against the cached beatmaps, while `mods` holds the
+/// mod combination the user plays most.
+pub struct SkillProfile {
+    pub stars: f64,
+    pub bpm: f64,
+    pub length: f64,
+    pub effective_bpm: f64,
+    pub mods: String,
+}
+
+pub fn build_skill_profile(
+    scores: &[(Score, usize, Beatmap, Beatmapset, CalculateResults)],
+) -> Option<SkillProfile> {
+    if scores.is_empty() {
+        return None;
+    }
+
+    // Weighted the same way pp is, so that the best plays shape the profile the most.
+    let mut total_weight = 0.0;
+    let mut stars = 0.0;
+    let mut bpm = 0.0;
+    let mut length = 0.0;
+    let mut effective_bpm = 0.0;
+    let mut mod_weights: HashMap<String, f64> = HashMap::new();
+
+    for (index, (score, _, beatmap, _, calculated)) in scores.iter().enumerate() {
+        let weight = 0.95_f64.powi(i32::try_from(index).ok()?);
+        total_weight += weight;
+        stars += beatmap.difficulty_rating * weight;
+        bpm += beatmap.bpm * weight;
+        length += f64::from(beatmap.drain) * weight;
+        effective_bpm += beatmap.bpm * calculated.clock_rate * weight;
+        *mod_weights.entry(score.mods.to_string()).or_default() += weight;
+    }
+
+    let mods = mod_weights
+        .into_iter()
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(mods, _)| mods)
+        .unwrap_or_default();
+
+    Some(SkillProfile {
+        stars: stars / total_weight,
+        bpm: bpm / total_weight,
+        length: length / total_weight,
+        effective_bpm: effective_bpm / total_weight,
+        mods,
+    })
+}
+
+fn is_tech(beatmap: &Beatmap) -> bool {
+    let objects = beatmap.count_circles + beatmap.count_sliders;
+    objects > 0 && f64::from(beatmap.count_sliders) / f64::from(objects) >= TECH_SLIDER_RATIO
+}
+
+pub async fn get_recommendations(
+    connection: &mut AsyncPgConnection,
+    profile: &SkillProfile,
+    mode: &str,
+    recommend_type: RecommendChoices,
+    played_map_ids: &HashSet<i64>,
+) -> Result<Vec<(Beatmap, Beatmapset)>, Error> {
+    let (min_stars, max_stars, max_length) = match recommend_type {
+        // Slightly easier and shorter than usual, which is where pp comes the fastest.
+        RecommendChoices::Farm => (
+            profile.stars - 0.3,
+            profile.stars + 0.1,
+            Some(profile.length),
+        ),
+        RecommendChoices::Aim => (profile.stars + 0.2, profile.stars + 0.6, None),
+        RecommendChoices::Tech => (profile.stars - 0.5, profile.stars + 0.2, None),
+    };
+
+    let search = BeatmapSearch {
+        mode: Some(mode.to_owned()),
+        status: Some(String::from("Ranked")),
+        min_stars: Some(min_stars),
+        max_stars: Some(max_stars),
+        min_bpm: Some(profile.bpm * 0.85),
+        max_bpm: Some(profile.bpm * 1.15),
+        max_length: max_length.map(|length| length.round() as i32),
+        ..Default::default()
+    };
+
+    let candidates = beatmaps::search(connection, &search, CANDIDATE_LIMIT).await?;
+
+    Ok(candidates
+        .into_iter()
+        .filter(|(beatmap, _)| !played_map_ids.contains(&beatmap.id))
+        .filter(|(beatmap, _)| {
+            !matches!(recommend_type, RecommendChoices::Tech) || is_tech(beatmap)
+        })
+        .take(RECOMMENDATION_LIMIT)
+        .collect())
+}
+
+pub fn format_skill_profile(profile: &SkillProfile) -> Result<String, Error> {
+    Ok(format!(
+        "{}★ • {} BPM • {}:{:02} • +{}",
+        remove_trailing_zeros(profile.stars, 2)?,
+        profile.effective_bpm.round(),
+        (profile.length.round() as i64) / 60,
+        (profile.length.round() as i64) % 60,
+        if profile.mods.is_empty() {
+            "NM"
+        } else {
+            &profile.mods
+        }
+    ))
+}
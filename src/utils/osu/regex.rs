@@ -10,6 +10,10 @@ static BEATMAPSET_URL_PATTERN_V2: OnceLock<Regex> = OnceLock::new();
 
 static BEATMAP_URL_PATTERN_V2: OnceLock<Regex> = OnceLock::new();
 
+static MATCH_URL_PATTERN: OnceLock<Regex> = OnceLock::new();
+
+static EDITOR_TIMESTAMP_PATTERN: OnceLock<Regex> = OnceLock::new();
+
 pub struct BeatmapInfo {
     pub beatmapset_id: Option<i64>,
     pub beatmap_id: Option<i64>,
@@ -144,3 +148,36 @@ pub fn get_beatmap_info(url: &str) -> Result<BeatmapInfo, Error> {
         })
     }
 }
+
+pub fn get_match_id(input: &str) -> Option<u32> {
+    let match_pattern = MATCH_URL_PATTERN.get_or_init(|| {
+        Regex::new(r"^(?:https?://osu\.ppy\.sh/(?:community/matches|mp)/)?(?P<match_id>\d+)/?$")
+            .unwrap()
+    });
+
+    match_pattern
+        .captures(input.trim())?
+        .name("match_id")?
+        .as_str()
+        .parse::<u32>()
+        .ok()
+}
+
+/// Finds an osu! editor timestamp like `01:23:456 (1,2,3) -` and returns it in milliseconds.
+pub fn get_editor_timestamp(input: &str) -> Option<f64> {
+    let timestamp_pattern = EDITOR_TIMESTAMP_PATTERN.get_or_init(|| {
+        Regex::new(r"(?:^|[^\d:])(?P<minutes>\d{1,3}):(?P<seconds>\d{2})[:.](?P<milliseconds>\d{3})(?:$|[^\d:])")
+            .unwrap()
+    });
+
+    let captures = timestamp_pattern.captures(input)?;
+    let minutes = captures.name("minutes")?.as_str().parse::<f64>().ok()?;
+    let seconds = captures.name("seconds")?.as_str().parse::<f64>().ok()?;
+    let milliseconds = captures
+        .name("milliseconds")?
+        .as_str()
+        .parse::<f64>()
+        .ok()?;
+
+    Some((minutes * 60.0 + seconds) * 1000.0 + milliseconds)
+}
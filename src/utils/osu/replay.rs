@@ -0,0 +1,370 @@
+use crate::Error;
+use chrono::{DateTime, Utc};
+use rosu_pp::model::hit_object::HitObjectKind;
+use rosu_v2::model::GameMode;
+use std::io::Cursor;
+
+const WINDOWS_TICKS_AT_UNIX_EPOCH: i64 = 621_355_968_000_000_000;
+
+const MOD_EASY: u32 = 1 << 1;
+const MOD_HARD_ROCK: u32 = 1 << 4;
+const MOD_TARGET_PRACTICE: u32 = 1 << 23;
+
+const KEY_M1: u32 = 1 << 0;
+const KEY_M2: u32 = 1 << 1;
+
+pub struct ReplayFrame {
+    pub time: f64,
+    pub x: f32,
+    pub y: f32,
+    pub keys: u32,
+}
+
+pub struct Replay {
+    pub mode: GameMode,
+    pub beatmap_md5: String,
+    pub player_name: String,
+    pub n300: u32,
+    pub n100: u32,
+    pub n50: u32,
+    pub ngeki: u32,
+    pub nkatu: u32,
+    pub nmiss: u32,
+    pub score: u32,
+    pub max_combo: u32,
+    pub perfect: bool,
+    pub mods: u32,
+    pub timestamp: DateTime<Utc>,
+    pub frames: Vec<ReplayFrame>,
+}
+
+pub struct HitError {
+    pub time: f64,
+    pub error: Option<f64>,
+}
+
+pub struct HitWindows {
+    pub great: f64,
+    pub ok: f64,
+    pub meh: f64,
+    pub radius: f32,
+}
+
+struct ReplayReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> ReplayReader<'a> {
+    fn new(bytes: &'a [u8]) -> ReplayReader<'a> {
+        ReplayReader { bytes, position: 0 }
+    }
+
+    fn read_bytes(&mut self, length: usize) -> Result<&'a [u8], Error> {
+        let end = self
+            .position
+            .checked_add(length)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or("Unexpected end of replay file")?;
+        let bytes = &self.bytes[self.position..end];
+        self.position = end;
+        Ok(bytes)
+    }
+
+    fn read_u8(&mut self) -> Result<u8, Error> {
+        Ok(self.read_bytes(1)?[0])
+    }
+
+    fn read_u16(&mut self) -> Result<u16, Error> {
+        Ok(u16::from_le_bytes(self.read_bytes(2)?.try_into()?))
+    }
+
+    fn read_i32(&mut self) -> Result<i32, Error> {
+        Ok(i32::from_le_bytes(self.read_bytes(4)?.try_into()?))
+    }
+
+    fn read_i64(&mut self) -> Result<i64, Error> {
+        Ok(i64::from_le_bytes(self.read_bytes(8)?.try_into()?))
+    }
+
+    fn read_uleb128(&mut self) -> Result<usize, Error> {
+        let mut result = 0;
+        let mut shift = 0;
+        loop {
+            let byte = self.read_u8()?;
+            result |= usize::from(byte & 0x7F) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(result);
+            }
+            shift += 7;
+            if shift > 63 {
+                return Err(Error::from("Invalid string length in replay file"));
+            }
+        }
+    }
+
+    fn read_string(&mut self) -> Result<String, Error> {
+        match self.read_u8()? {
+            0x00 => Ok(String::new()),
+            0x0b => {
+                let length = self.read_uleb128()?;
+                Ok(String::from_utf8(self.read_bytes(length)?.to_vec())?)
+            }
+            _ => Err(Error::from("Invalid string in replay file")),
+        }
+    }
+}
+
+pub fn parse_replay(bytes: &[u8]) -> Result<Replay, Error> {
+    let mut reader = ReplayReader::new(bytes);
+
+    let mode = reader.read_u8()?;
+    if mode > 3 {
+        return Err(Error::from("Invalid mode in replay file"));
+    }
+    let _version = reader.read_i32()?;
+    let beatmap_md5 = reader.read_string()?;
+    let player_name = reader.read_string()?;
+    let _replay_md5 = reader.read_string()?;
+    let n300 = u32::from(reader.read_u16()?);
+    let n100 = u32::from(reader.read_u16()?);
+    let n50 = u32::from(reader.read_u16()?);
+    let ngeki = u32::from(reader.read_u16()?);
+    let nkatu = u32::from(reader.read_u16()?);
+    let nmiss = u32::from(reader.read_u16()?);
+    let score = u32::try_from(reader.read_i32()?)?;
+    let max_combo = u32::from(reader.read_u16()?);
+    let perfect = reader.read_u8()? == 1;
+    let mods = u32::try_from(reader.read_i32()?)?;
+    let _life_bar = reader.read_string()?;
+    let ticks = reader.read_i64()?;
+    let compressed_length = usize::try_from(reader.read_i32()?)?;
+    let compressed_frames = reader.read_bytes(compressed_length)?;
+    let _online_score_id = reader.read_i64()?;
+    if mods & MOD_TARGET_PRACTICE != 0 {
+        let _target_practice_accuracy = reader.read_bytes(8)?;
+    }
+
+    let timestamp = DateTime::from_timestamp((ticks - WINDOWS_TICKS_AT_UNIX_EPOCH) / 10_000_000, 0)
+        .ok_or("Invalid timestamp in replay file")?;
+
+    Ok(Replay {
+        mode: GameMode::from(mode),
+        beatmap_md5,
+        player_name,
+        n300,
+        n100,
+        n50,
+        ngeki,
+        nkatu,
+        nmiss,
+        score,
+        max_combo,
+        perfect,
+        mods,
+        timestamp,
+        frames: decompress_frames(compressed_frames)?,
+    })
+}
+
+fn decompress_frames(compressed: &[u8]) -> Result<Vec<ReplayFrame>, Error> {
+    if compressed.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut decompressed = Vec::new();
+    lzma_rs::lzma_decompress(&mut Cursor::new(compressed), &mut decompressed)?;
+    let decompressed = String::from_utf8(decompressed)?;
+
+    let mut frames = Vec::new();
+    let mut time = 0.0;
+    for frame in decompressed.split(',') {
+        let values = frame.split('|').collect::<Vec<&str>>();
+        if values.len() != 4 {
+            continue;
+        }
+        let delta = values[0].parse::<f64>()?;
+        // The RNG seed is stored as a final frame with a delta of -12345.
+        if delta == -12345.0 {
+            continue;
+        }
+        time += delta;
+        frames.push(ReplayFrame {
+            time,
+            x: values[1].parse::<f32>()?,
+            y: values[2].parse::<f32>()?,
+            keys: values[3].parse::<f64>()? as u32,
+        });
+    }
+
+    Ok(frames)
+}
+
+impl Replay {
+    pub fn accuracy(&self) -> f64 {
+        let (numerator, denominator) = match self.mode {
+            GameMode::Osu => (
+                f64::from(300 * self.n300 + 100 * self.n100 + 50 * self.n50),
+                f64::from(300 * (self.n300 + self.n100 + self.n50 + self.nmiss)),
+            ),
+            GameMode::Taiko => (
+                f64::from(self.n300) + f64::from(self.n100) * 0.5,
+                f64::from(self.n300 + self.n100 + self.nmiss),
+            ),
+            GameMode::Catch => (
+                f64::from(self.n300 + self.n100 + self.n50),
+                f64::from(self.n300 + self.n100 + self.n50 + self.nkatu + self.nmiss),
+            ),
+            GameMode::Mania => (
+                f64::from(
+                    300 * (self.ngeki + self.n300)
+                        + 200 * self.nkatu
+                        + 100 * self.n100
+                        + 50 * self.n50,
+                ),
+                f64::from(
+                    300 * (self.ngeki + self.n300 + self.nkatu + self.n100 + self.n50 + self.nmiss),
+                ),
+            ),
+        };
+
+        if denominator == 0.0 {
+            return 0.0;
+        }
+
+        numerator / denominator * 100.0
+    }
+
+    pub fn potential_accuracy(&self) -> Option<f64> {
+        match self.mode {
+            GameMode::Osu => {
+                let total = self.n300 + self.n100 + self.n50 + self.nmiss;
+                if total == 0 {
+                    return None;
+                }
+                Some(
+                    f64::from(300 * (self.n300 + self.nmiss) + 100 * self.n100 + 50 * self.n50)
+                        / f64::from(300 * total)
+                        * 100.0,
+                )
+            }
+            _ => None,
+        }
+    }
+
+    pub fn total_hits(&self) -> u32 {
+        match self.mode {
+            GameMode::Osu => self.n300 + self.n100 + self.n50 + self.nmiss,
+            GameMode::Taiko => self.n300 + self.n100 + self.nmiss,
+            GameMode::Catch => self.n300 + self.n100 + self.nmiss,
+            GameMode::Mania => {
+                self.ngeki + self.n300 + self.nkatu + self.n100 + self.n50 + self.nmiss
+            }
+        }
+    }
+
+    pub fn is_hard_rock(&self) -> bool {
+        self.mods & MOD_HARD_ROCK != 0
+    }
+
+    fn adjust_difficulty(&self, value: f32, hard_rock_multiplier: f32) -> f32 {
+        if self.is_hard_rock() {
+            (value * hard_rock_multiplier).min(10.0)
+        } else if self.mods & MOD_EASY != 0 {
+            value * 0.5
+        } else {
+            value
+        }
+    }
+
+    pub fn hit_windows(&self, map: &rosu_pp::Beatmap) -> HitWindows {
+        let od = f64::from(self.adjust_difficulty(map.od, 1.4));
+        let cs = self.adjust_difficulty(map.cs, 1.3);
+
+        HitWindows {
+            great: 80.0 - 6.0 * od,
+            ok: 140.0 - 8.0 * od,
+            meh: 200.0 - 10.0 * od,
+            radius: 54.4 - 4.48 * cs,
+        }
+    }
+
+    pub fn hit_errors(&self, map: &rosu_pp::Beatmap, hit_windows: &HitWindows) -> Vec<HitError> {
+        if self.mode != GameMode::Osu {
+            return Vec::new();
+        }
+
+        let mut presses = Vec::new();
+        let mut previous_keys = 0;
+        for frame in &self.frames {
+            let keys = frame.keys & (KEY_M1 | KEY_M2);
+            if keys & !previous_keys != 0 {
+                presses.push(frame);
+            }
+            previous_keys = keys;
+        }
+
+        let mut hit_errors = Vec::new();
+        let mut press_index = 0;
+        for hit_object in &map.hit_objects {
+            if matches!(hit_object.kind, HitObjectKind::Spinner { .. }) {
+                continue;
+            }
+
+            let object_y = if self.is_hard_rock() {
+                384.0 - hit_object.pos.y
+            } else {
+                hit_object.pos.y
+            };
+
+            while press_index < presses.len()
+                && presses[press_index].time < hit_object.start_time - hit_windows.meh
+            {
+                press_index += 1;
+            }
+
+            let mut error = None;
+            let mut candidate = press_index;
+            while candidate < presses.len()
+                && presses[candidate].time <= hit_object.start_time + hit_windows.meh
+            {
+                let press = presses[candidate];
+                let distance =
+                    ((press.x - hit_object.pos.x).powi(2) + (press.y - object_y).powi(2)).sqrt();
+                if distance <= hit_windows.radius {
+                    error = Some(press.time - hit_object.start_time);
+                    press_index = candidate + 1;
+                    break;
+                }
+                candidate += 1;
+            }
+
+            hit_errors.push(HitError {
+                time: hit_object.start_time,
+                error,
+            });
+        }
+
+        hit_errors
+    }
+}
+
+pub fn unstable_rate(hit_errors: &[HitError], clock_rate: f64) -> Option<f64> {
+    let errors = hit_errors
+        .iter()
+        .filter_map(|hit| hit.error)
+        .collect::<Vec<f64>>();
+    if errors.len() < 2 {
+        return None;
+    }
+
+    let count = errors.len() as f64;
+    let mean = errors.iter().sum::<f64>() / count;
+    let variance = errors
+        .iter()
+        .map(|error| (error - mean).powi(2))
+        .sum::<f64>()
+        / count;
+
+    Some(variance.sqrt() * 10.0 / clock_rate)
+}
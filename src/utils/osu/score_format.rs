@@ -2,37 +2,38 @@ use crate::Error;
 use crate::models::beatmaps::Beatmap;
 use crate::models::beatmapsets::Beatmapset;
 use crate::utils::misc::remove_trailing_zeros;
+use crate::utils::osu::calculate::SimulatedScore;
 use crate::utils::osu::misc::is_perfect;
 use crate::utils::osu::misc_format::{fmt_with_settings, format_beatmap_link, format_footer};
 use crate::utils::osu::pp::CalculateResults;
+use crate::utils::osu::replay::Replay;
 use num_format::{Locale, ToFormattedString};
 use rosu_v2::model::GameMode;
-use rosu_v2::prelude::{Grade, Score};
+use rosu_v2::prelude::{GameModsIntermode, Grade, Score};
 use std::cmp;
 
-pub fn format_score_statistic(score: &Score, pp: &CalculateResults) -> Result<String, Error> {
-    let color = match score.build_id {
-        None => {
-            if score.legacy_perfect.ok_or(format!(
-                "Couldn't get legacy_perfect for score id: {}",
-                score.id
-            ))? {
-                "\u{001b}[0;32m"
-            } else {
-                "\u{001b}[0;31m"
-            }
-        }
-        Some(_) => {
-            if is_perfect(&score.statistics) {
-                "\u{001b}[0;32m"
-            } else {
-                "\u{001b}[0;31m"
-            }
-        }
+/// Hit counts in the order a stable replay stores them, so scores and replays can share one
+/// formatter.
+struct HitCounts {
+    accuracy: f64,
+    perfect: bool,
+    combo: u32,
+    ngeki: u32,
+    n300: u32,
+    nkatu: u32,
+    n100: u32,
+    n50: u32,
+    nmiss: u32,
+}
+
+fn format_statistic(mode: GameMode, hits: &HitCounts, max_combo: u32) -> Result<String, Error> {
+    let color = if hits.perfect {
+        "\u{001b}[0;32m"
+    } else {
+        "\u{001b}[0;31m"
     };
 
-    let max_combo = pp.max_combo;
-    let accuracy_string = format!("{}%", remove_trailing_zeros(score.accuracy.into(), 2)?);
+    let accuracy_string = format!("{}%", remove_trailing_zeros(hits.accuracy, 2)?);
     let gap = if accuracy_string.len() < 6 {
         " ".repeat(cmp::max(accuracy_string.len() - 3 + 1, 2))
     } else {
@@ -45,53 +46,69 @@ pub fn format_score_statistic(score: &Score, pp: &CalculateResults) -> Result<St
         " ".repeat(gap.len())
     };
 
-    match score.mode {
+    match mode {
         GameMode::Osu => Ok(format!(
             "acc{gap}300s  100s  50s  miss  combo\
                 \n{color}{}{stat_gap}{:<6}{:<6}{:<5}{:<6}{}/{}",
-            accuracy_string,
-            score.statistics.great,
-            score.statistics.ok,
-            score.statistics.meh,
-            score.statistics.miss,
-            score.max_combo,
-            max_combo
+            accuracy_string, hits.n300, hits.n100, hits.n50, hits.nmiss, hits.combo, max_combo
         )),
         GameMode::Taiko => Ok(format!(
             "acc{gap}great  good  miss  combo\
             \n{color}{}{stat_gap}{:<7}{:<6}{:<6}{}/{}",
-            accuracy_string,
-            score.statistics.great,
-            score.statistics.ok,
-            score.statistics.miss,
-            score.max_combo,
-            max_combo
+            accuracy_string, hits.n300, hits.n100, hits.nmiss, hits.combo, max_combo
         )),
         GameMode::Mania => Ok(format!(
             "acc{gap}max   300s  200s  100s  50s  miss\
         \n{color}{}{stat_gap}{:<6}{:<6}{:<6}{:<6}{:<5}{:<6}",
-            accuracy_string,
-            score.statistics.perfect,
-            score.statistics.great,
-            score.statistics.good,
-            score.statistics.ok,
-            score.statistics.meh,
-            score.statistics.miss
+            accuracy_string, hits.ngeki, hits.n300, hits.nkatu, hits.n100, hits.n50, hits.nmiss
         )),
         GameMode::Catch => Ok(format!(
             "acc{gap}fruits ticks drpm miss combo\
            \n{color}{}{stat_gap}{:<7}{:<6}{:<5}{:<5}{}/{}",
-            accuracy_string,
-            score.statistics.great,
-            score.statistics.large_tick_hit,
-            score.statistics.small_tick_hit,
-            score.statistics.small_tick_miss,
-            score.max_combo,
-            max_combo
+            accuracy_string, hits.n300, hits.n100, hits.n50, hits.nkatu, hits.combo, max_combo
         )),
     }
 }
 
+pub fn format_score_statistic(score: &Score, pp: &CalculateResults) -> Result<String, Error> {
+    let perfect = match score.build_id {
+        None => score.legacy_perfect.ok_or(format!(
+            "Couldn't get legacy_perfect for score id: {}",
+            score.id
+        ))?,
+        Some(_) => is_perfect(&score.statistics),
+    };
+
+    let statistics = &score.statistics;
+    // Catch stores its ticks and droplets where the other modes keep their judgements.
+    let hits = match score.mode {
+        GameMode::Catch => HitCounts {
+            accuracy: score.accuracy.into(),
+            perfect,
+            combo: score.max_combo,
+            ngeki: 0,
+            n300: statistics.great,
+            nkatu: statistics.small_tick_miss,
+            n100: statistics.large_tick_hit,
+            n50: statistics.small_tick_hit,
+            nmiss: statistics.miss,
+        },
+        _ => HitCounts {
+            accuracy: score.accuracy.into(),
+            perfect,
+            combo: score.max_combo,
+            ngeki: statistics.perfect,
+            n300: statistics.great,
+            nkatu: statistics.good,
+            n100: statistics.ok,
+            n50: statistics.meh,
+            nmiss: statistics.miss,
+        },
+    };
+
+    format_statistic(score.mode, &hits, pp.max_combo)
+}
+
 pub fn format_score_info(
     score: &Score,
     beatmap: &Beatmap,
@@ -146,16 +163,22 @@ pub fn format_score_info(
         String::new()
     };
 
+    let if_fc = match format_if_fc(score, pp)? {
+        Some(if_fc) => format!("\n{if_fc}"),
+        None => String::new(),
+    };
+
     Ok(format!(
         "{}\
-        **{}pp {}★, {} {}+{} {}**",
+        **{}pp {}★, {} {}+{} {}**{}",
         title,
         remove_trailing_zeros(score_pp, 2)?,
         remove_trailing_zeros(stars, 2)?,
         grade,
         scoreboard_rank,
         fmt_with_settings(&score.mods)?,
-        score.score.to_formatted_string(&Locale::en)
+        score.score.to_formatted_string(&Locale::en),
+        if_fc
     ))
 }
 
@@ -231,9 +254,14 @@ pub fn format_minimal_score(
         String::new()
     };
 
+    let if_fc = match format_if_fc(score, pp)? {
+        Some(if_fc) => format!("{if_fc}\n"),
+        None => String::new(),
+    };
+
     Ok(format!(
         "{title}\
-     **{score_pp}pp {stars}\u{2605}, {maxcombo}/{max_combo} {rank} {acc} {scoreboard_rank}+{mods}**\n",
+     **{score_pp}pp {stars}\u{2605}, {maxcombo}/{max_combo} {rank} {acc} {scoreboard_rank}+{mods}**\n{if_fc}",
         title = title,
         mods = score.mods,
         acc = accuracy_string,
@@ -288,6 +316,153 @@ pub fn format_score_list(
     Ok(formatted_list.join("\n"))
 }
 
+pub fn format_replay_statistic(replay: &Replay, pp: &CalculateResults) -> Result<String, Error> {
+    let hits = HitCounts {
+        accuracy: replay.accuracy(),
+        perfect: replay.perfect,
+        combo: replay.max_combo,
+        ngeki: replay.ngeki,
+        n300: replay.n300,
+        nkatu: replay.nkatu,
+        n100: replay.n100,
+        n50: replay.n50,
+        nmiss: replay.nmiss,
+    };
+
+    format_statistic(replay.mode, &hits, pp.max_combo)
+}
+
+pub fn format_replay(
+    replay: &Replay,
+    pp: &CalculateResults,
+    unstable_rate: Option<f64>,
+) -> Result<String, Error> {
+    let mods = GameModsIntermode::from_bits(replay.mods).to_string();
+
+    let unstable_rate = if let Some(unstable_rate) = unstable_rate {
+        format!(
+            "Estimated UR: {}\n",
+            remove_trailing_zeros(unstable_rate, 2)?
+        )
+    } else {
+        String::new()
+    };
+
+    Ok(format!(
+        "**{}pp {}★, +{} {}**\n```ansi\n{}```{}",
+        remove_trailing_zeros(pp.pp, 2)?,
+        remove_trailing_zeros(pp.total_stars, 2)?,
+        if mods.is_empty() { "NM" } else { &mods },
+        replay.score.to_formatted_string(&Locale::en),
+        format_replay_statistic(replay, pp)?,
+        unstable_rate
+    ))
+}
+
+pub fn format_simulated_score(
+    simulated: &SimulatedScore,
+    mode: GameMode,
+    mods: &str,
+    pp: &CalculateResults,
+) -> Result<String, Error> {
+    let accuracy = match simulated.acc {
+        Some(acc) => format!("{}%", remove_trailing_zeros(acc, 2)?),
+        None => String::from("-"),
+    };
+    let combo = simulated.combo.unwrap_or(pp.max_combo);
+    let count =
+        |count: Option<u32>| count.map_or_else(|| String::from("-"), |count| count.to_string());
+
+    let statistics = match mode {
+        GameMode::Osu => format!(
+            "acc     300s  100s  50s  miss  combo\n{:<8}{:<6}{:<6}{:<5}{:<6}{}/{}",
+            accuracy,
+            count(simulated.n300),
+            count(simulated.n100),
+            count(simulated.n50),
+            simulated.nmiss.unwrap_or(0),
+            combo,
+            pp.max_combo
+        ),
+        GameMode::Taiko => format!(
+            "acc     great  good  miss  combo\n{:<8}{:<7}{:<6}{:<6}{}/{}",
+            accuracy,
+            count(simulated.n300),
+            count(simulated.n100),
+            simulated.nmiss.unwrap_or(0),
+            combo,
+            pp.max_combo
+        ),
+        GameMode::Mania => format!(
+            "acc     300s  100s  50s  miss\n{:<8}{:<6}{:<6}{:<5}{}",
+            accuracy,
+            count(simulated.n300),
+            count(simulated.n100),
+            count(simulated.n50),
+            simulated.nmiss.unwrap_or(0)
+        ),
+        GameMode::Catch => format!(
+            "acc     fruits ticks drpm miss combo\n{:<8}{:<7}{:<6}{:<5}{:<5}{}/{}",
+            accuracy,
+            count(simulated.n300),
+            count(simulated.n100),
+            count(simulated.n50),
+            simulated.nmiss.unwrap_or(0),
+            combo,
+            pp.max_combo
+        ),
+    };
+
+    Ok(format!(
+        "**{}pp {}★, +{}**\n```ansi\n{}```",
+        remove_trailing_zeros(pp.pp, 2)?,
+        remove_trailing_zeros(pp.total_stars, 2)?,
+        mods,
+        statistics
+    ))
+}
+
+pub fn format_server_score(score: &Score, pp: &CalculateResults) -> Result<String, Error> {
+    let score_pp = match score.pp {
+        Some(api_pp) => f64::from(api_pp),
+        _ => pp.pp,
+    };
+
+    Ok(format!(
+        "**{}pp**, {} {}% {}/{}x +{} {} <t:{}:R>",
+        remove_trailing_zeros(score_pp, 2)?,
+        get_grade_string(score.grade, score.passed),
+        remove_trailing_zeros(score.accuracy.into(), 2)?,
+        score.max_combo,
+        pp.max_combo,
+        fmt_with_settings(&score.mods)?,
+        score.score.to_formatted_string(&Locale::en),
+        score.ended_at.unix_timestamp()
+    ))
+}
+
+fn is_full_combo(score: &Score) -> bool {
+    match score.build_id {
+        None => score.legacy_perfect.unwrap_or(false),
+        Some(_) => is_perfect(&score.statistics),
+    }
+}
+
+fn format_if_fc(score: &Score, pp: &CalculateResults) -> Result<Option<String>, Error> {
+    if is_full_combo(score) {
+        return Ok(None);
+    }
+
+    match (pp.fc_pp, pp.ss_pp) {
+        (Some(fc_pp), Some(ss_pp)) => Ok(Some(format!(
+            "If FC: {}pp • SS: {}pp",
+            remove_trailing_zeros(fc_pp, 2)?,
+            remove_trailing_zeros(ss_pp, 2)?
+        ))),
+        _ => Ok(None),
+    }
+}
+
 fn get_grade_string(grade: Grade, passed: bool) -> String {
     if grade.to_string() != "F" && !passed {
         format!("{grade} (Failed)")
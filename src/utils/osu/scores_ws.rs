@@ -1,15 +1,15 @@
 use crate::Error;
 use crate::models::linked_osu_profiles::LinkedOsuProfile;
 use crate::models::osu_notifications::{NewOsuNotification, OsuNotification};
+use crate::models::osu_user_snapshots::NewOsuUserSnapshot;
 use crate::models::osu_users::OsuUser;
-use crate::utils::db::{linked_osu_profiles, osu_notifications};
-use crate::utils::db::{osu_guild_channels, osu_users};
+use crate::utils::db::osu_score_rules::ScoreRuleCheck;
+use crate::utils::db::{linked_osu_profiles, osu_notification_preferences, osu_notifications};
+use crate::utils::db::{osu_guild_channels, osu_score_rules, osu_user_snapshots, osu_users};
 use crate::utils::osu::caching::get_beatmap;
 use crate::utils::osu::calculate::calculate;
 use crate::utils::osu::embeds::create_embed;
-use crate::utils::osu::misc::{
-    add_profile_data, calculate_potential_acc, gamemode_from_string, get_score_position,
-};
+use crate::utils::osu::misc::{add_profile_data, calculate_potential_acc, get_score_position};
 use crate::utils::osu::misc_format::{
     format_beatmap_link, format_diff, format_footer, format_user_link,
 };
@@ -139,7 +139,9 @@ impl ScoresWs {
         linked_profile: &LinkedOsuProfile,
         mode: GameMode,
     ) -> Result<OsuUser, Error> {
-        if let Ok(osu_user) = osu_users::read(connection, linked_profile.osu_id).await {
+        if let Ok(osu_user) =
+            osu_users::read(connection, linked_profile.osu_id, &mode.to_string()).await
+        {
             Ok(osu_user)
         } else {
             add_profile_data(
@@ -217,8 +219,13 @@ impl ScoresWs {
                 continue;
             }
 
-            for osu_user_id in users {
-                let linked_profile = match linked_osu_profiles::read(connection, osu_user_id).await
+            for discord_id in users {
+                let linked_profile = match linked_osu_profiles::read_account(
+                    connection,
+                    discord_id,
+                    i64::from(score.user_id),
+                )
+                .await
                 {
                     Ok(profile) => profile,
                     Err(why) => {
@@ -227,19 +234,23 @@ impl ScoresWs {
                     }
                 };
 
-                let Some(mode) = gamemode_from_string(&linked_profile.mode) else {
-                    error!(
-                        "Couldn't convert mode {} for user {}",
-                        linked_profile.mode, linked_profile.id
-                    );
+                if osu_notification_preferences::read(connection, linked_profile.id)
+                    .await
+                    .is_ok_and(|preference| {
+                        preference.is_some_and(|preference| preference.is_paused())
+                    })
+                {
                     continue;
-                };
+                }
 
-                if score.mode != mode {
+                if !linked_profile.tracked_modes().contains(&score.mode) {
                     continue;
                 }
 
-                let osu_user = match self.get_osu_user(connection, &linked_profile, mode).await {
+                let osu_user = match self
+                    .get_osu_user(connection, &linked_profile, score.mode)
+                    .await
+                {
                     Ok(osu_user) => osu_user,
                     Err(why) => {
                         error!("{}", why);
@@ -302,6 +313,7 @@ impl ScoresWs {
                 connection,
             )
             .await?;
+            osu_user_snapshots::create(connection, &NewOsuUserSnapshot::from(&new)).await?;
 
             self.notify_single_score(
                 &(score, score_position),
@@ -327,9 +339,6 @@ impl ScoresWs {
     ) -> Result<(), Error> {
         let score_id = score.0.id;
 
-        let gamemode = gamemode_from_string(&linked_profile.mode)
-            .ok_or("Failed to parse gamemode in notify_single_score function")?;
-
         let mut recent_scores = crate::utils::osu::tracking::SCORE_NOTIFICATIONS
             .get_or_init(DashMap::new)
             .entry(linked_profile.osu_id)
@@ -376,10 +385,23 @@ impl ScoresWs {
         let formatted_score = format!(
             "{}{}\n<t:{}:R>",
             score_info,
-            format_diff(new, old, gamemode)?,
+            format_diff(new, old, score.0.mode)?,
             score.0.ended_at.unix_timestamp()
         );
 
+        let formatted_score_without_diff = format!(
+            "{}\n<t:{}:R>",
+            score_info,
+            score.0.ended_at.unix_timestamp()
+        );
+
+        let rule_check = ScoreRuleCheck {
+            mode: score.0.mode,
+            pp: pp.pp,
+            position: Some(score.1),
+            ranked: matches!(beatmap.0.status.as_str(), "Ranked" | "Approved"),
+        };
+
         let item = NewOsuNotification {
             id: linked_profile.osu_id,
             last_pp: Utc.timestamp_nanos(i64::try_from(score.0.ended_at.unix_timestamp_nanos())?),
@@ -396,6 +418,8 @@ impl ScoresWs {
             linked_profile,
             &thumbnail,
             &formatted_score,
+            &formatted_score_without_diff,
+            &rule_check,
             &footer,
             &author_text,
             Some(title),
@@ -413,23 +437,78 @@ impl ScoresWs {
         linked_profile: &LinkedOsuProfile,
         thumbnail: &str,
         formatted_score: &str,
+        formatted_score_without_diff: &str,
+        rule_check: &ScoreRuleCheck,
         footer: &str,
         author_text: &str,
         title: Option<String>,
         title_url: Option<String>,
         new: &OsuUser,
     ) -> Result<(), Error> {
+        let preference = osu_notification_preferences::read(connection, linked_profile.id).await?;
+        let user_link = format_user_link(new.id);
+
+        if preference
+            .as_ref()
+            .is_some_and(|preference| preference.direct_message)
+        {
+            let embed = create_embed(
+                BLUE,
+                thumbnail,
+                formatted_score,
+                footer,
+                &new.avatar_url,
+                author_text,
+                &user_link,
+                title,
+                title_url,
+            );
+
+            if let Err(why) = UserId::new(u64::try_from(linked_profile.id)?)
+                .direct_message(&self.http, CreateMessage::new().embed(embed))
+                .await
+            {
+                error!(
+                    "Failed to send notification to user {}: {}",
+                    linked_profile.id, why
+                );
+            }
+
+            return Ok(());
+        }
+
         for guild_id in self.cache.guilds() {
-            if let Ok(guild_channels) =
-                osu_guild_channels::read(connection, i64::try_from(guild_id.get())?).await
+            let db_guild_id = i64::try_from(guild_id.get())?;
+            if preference
+                .as_ref()
+                .is_some_and(|preference| preference.is_muted(db_guild_id))
+            {
+                continue;
+            }
+
+            if let Ok(guild_channels) = osu_guild_channels::read(connection, db_guild_id).await
+                && (linked_profile.verified || !guild_channels.require_verified)
                 && let Some(score_channels) = &guild_channels.score_channel
             {
+                let rules = osu_score_rules::get_guild(connection, guild_channels.guild_id).await?;
+
                 for score_channel in score_channels
                     .iter()
                     .flatten()
                     .copied()
                     .collect::<Vec<i64>>()
                 {
+                    let rule = rules.iter().find(|rule| rule.channel_id == score_channel);
+                    if rule.is_some_and(|rule| !rule.allows(rule_check)) {
+                        continue;
+                    }
+
+                    let description = if rule.is_none_or(|rule| rule.show_diff) {
+                        formatted_score
+                    } else {
+                        formatted_score_without_diff
+                    };
+
                     if let Ok(member) = guild_id
                         .member(
                             (Some(&self.cache), self.http.http()),
@@ -439,12 +518,10 @@ impl ScoresWs {
                     {
                         let color = member.colour(&self.cache).unwrap_or(BLUE);
 
-                        let user_link = format_user_link(new.id);
-
                         let embed = create_embed(
                             color,
                             thumbnail,
-                            formatted_score,
+                            description,
                             footer,
                             &new.avatar_url,
                             author_text,
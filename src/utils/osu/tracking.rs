@@ -1,16 +1,25 @@
 use crate::models::beatmaps::Beatmap;
 use crate::models::beatmapsets::Beatmapset;
+use crate::models::followed_mappers::FollowedMapper;
 use crate::models::linked_osu_profiles::LinkedOsuProfile;
 use crate::models::osu_files::OsuFile;
 use crate::models::osu_notifications::NewOsuNotification;
+use crate::models::osu_user_snapshots::NewOsuUserSnapshot;
 use crate::models::osu_users::OsuUser;
-use crate::utils::db::{linked_osu_profiles, osu_guild_channels, osu_notifications, osu_users};
+use crate::utils::db::osu_score_rules::ScoreRuleCheck;
+use crate::utils::db::{
+    beatmaps, followed_mappers, linked_osu_profiles, osu_guild_channels,
+    osu_notification_preferences, osu_notifications, osu_score_rules, osu_user_snapshots,
+    osu_users,
+};
 use crate::utils::osu::caching::{get_beatmap, get_updated_beatmapset};
 use crate::utils::osu::calculate::calculate;
 use crate::utils::osu::embeds::create_embed;
-use crate::utils::osu::map_format::{format_beatmapset, format_single_beatmap};
+use crate::utils::osu::map_format::{
+    format_beatmapset, format_beatmapset_diff, format_single_beatmap,
+};
 use crate::utils::osu::misc::{
-    add_profile_data, calculate_potential_acc, gamemode_from_string, get_osu_user, is_playing,
+    add_profile_data, calculate_potential_acc, get_osu_user, is_playing,
 };
 use crate::utils::osu::misc_format::{format_beatmap_link, format_footer, format_user_link};
 use crate::utils::osu::regex::get_beatmap_info;
@@ -20,6 +29,7 @@ use chrono::Utc;
 use dashmap::DashMap;
 use diesel_async::AsyncPgConnection;
 use diesel_async::pooled_connection::AsyncDieselConnectionManager;
+use foldhash::{HashMap, HashMapExt};
 use poise::serenity_prelude::model::colour::colours::roles::BLUE;
 use poise::serenity_prelude::{
     Cache, CacheHttp, CreateEmbed, CreateMessage, GenericChannelId, Http, UserId,
@@ -79,6 +89,9 @@ impl OsuTracker {
                     error!("Error occurred while running tracking loop: {}", why);
                 }
             }
+            if let Err(why) = self.notify_followed_mappers(connection).await {
+                error!("Error occurred while notifying followed mappers: {}", why);
+            }
         }
     }
 
@@ -96,79 +109,121 @@ impl OsuTracker {
             _ => return Ok(()),
         };
 
-        if let Ok(mut profile) = osu_users::read(connection, linked_profile.osu_id).await {
-            if (Utc::now() - profile.time_cached).num_hours() > 24 {
-                add_profile_data(
-                    self.osu_client.clone(),
-                    u32::try_from(linked_profile.osu_id)?,
-                    gamemode_from_string(&linked_profile.mode)
-                        .ok_or("Failed to parse gamemode in update_user_data function")?,
-                    connection,
-                )
-                .await?;
-                return Ok(());
+        // Every tracked mode has its own cached profile, which gets refreshed once a day. Like
+        // when a profile is first added, nothing is posted on a tick where one was refreshed.
+        let mut tracked_profile = None;
+        let mut refreshed = false;
+        for mode in linked_profile.tracked_modes() {
+            match osu_users::read(connection, linked_profile.osu_id, &mode.to_string()).await {
+                Ok(profile) if (Utc::now() - profile.time_cached).num_hours() <= 24 => {
+                    tracked_profile.get_or_insert(profile);
+                }
+                _ => {
+                    let new = add_profile_data(
+                        self.osu_client.clone(),
+                        u32::try_from(linked_profile.osu_id)?,
+                        mode,
+                        connection,
+                    )
+                    .await?;
+                    osu_user_snapshots::create(connection, &NewOsuUserSnapshot::from(&new)).await?;
+                    refreshed = true;
+                }
             }
+        }
 
-            profile.ticks += 1;
+        if refreshed {
+            return Ok(());
+        }
 
-            let not_playing_skip = NOT_PLAYING_SKIP
-                .get_or_init(|| {
-                    env::var("NOT_PLAYING_SKIP")
-                        .unwrap_or_else(|_| String::from("10"))
-                        .parse::<i32>()
-                        .expect("Failed to parse tracking not playing skip.")
-                })
-                .to_owned();
+        let Some(mut profile) = tracked_profile else {
+            return Ok(());
+        };
 
-            if profile.ticks > not_playing_skip {
-                profile.ticks = 0;
-                osu_users::update_ticks(connection, profile.id, profile.ticks).await?;
-                return Ok(());
-            }
+        profile.ticks += 1;
 
-            if is_playing(&self.cache, user.id, linked_profile.home_guild)?
-                || (profile.ticks.eq(&not_playing_skip))
-            {
-                osu_users::update_ticks(connection, profile.id, profile.ticks).await?;
+        let not_playing_skip = NOT_PLAYING_SKIP
+            .get_or_init(|| {
+                env::var("NOT_PLAYING_SKIP")
+                    .unwrap_or_else(|_| String::from("10"))
+                    .parse::<i32>()
+                    .expect("Failed to parse tracking not playing skip.")
+            })
+            .to_owned();
 
-                if let Err(why) = self
-                    .notify_recent(&profile, connection, linked_profile)
-                    .await
+        if profile.ticks > not_playing_skip {
+            profile.ticks = 0;
+            osu_users::update_ticks(connection, profile.id, &profile.mode, profile.ticks).await?;
+            return Ok(());
+        }
+
+        if is_playing(&self.cache, user.id, linked_profile.home_guild)?
+            || (profile.ticks.eq(&not_playing_skip))
+        {
+            osu_users::update_ticks(connection, profile.id, &profile.mode, profile.ticks).await?;
+
+            if osu_notification_preferences::read(connection, linked_profile.id)
+                .await?
+                .is_some_and(|preference| preference.is_paused())
+            {
+                // Events from while notifications were paused shouldn't be posted once they're
+                // resumed.
+                if let Ok(last_notifications) =
+                    osu_notifications::read(connection, linked_profile.osu_id).await
                 {
-                    error!("Error occurred while running tracking loop: {}", why);
-                    return Ok(());
+                    let item = NewOsuNotification {
+                        id: linked_profile.osu_id,
+                        last_pp: last_notifications.last_pp,
+                        last_event: Utc::now(),
+                    };
+
+                    osu_notifications::update(connection, linked_profile.osu_id, &item).await?;
                 }
-            } else {
-                osu_users::update_ticks(connection, profile.id, profile.ticks).await?;
+
+                return Ok(());
+            }
+
+            if let Err(why) = self
+                .notify_recent(&profile, connection, linked_profile)
+                .await
+            {
+                error!("Error occurred while running tracking loop: {}", why);
+                return Ok(());
             }
         } else {
-            add_profile_data(
-                self.osu_client.clone(),
-                u32::try_from(linked_profile.osu_id)?,
-                gamemode_from_string(&linked_profile.mode)
-                    .ok_or("Failed to parse gamemode in update_user_data function")?,
-                connection,
-            )
-            .await?;
+            osu_users::update_ticks(connection, profile.id, &profile.mode, profile.ticks).await?;
         }
 
         Ok(())
     }
 
+    /// Returns the difficulties that were cached before the event, if it's an update, along with
+    /// the updated beatmapset.
     async fn get_notify_beatmapset(
         &mut self,
         connection: &mut AsyncPgConnection,
         beatmapset_url: &str,
-    ) -> Result<(Beatmapset, Vec<(Beatmap, OsuFile)>), Error> {
+        event_type: &EventType,
+    ) -> Result<(Option<Vec<Beatmap>>, (Beatmapset, Vec<(Beatmap, OsuFile)>)), Error> {
         let beatmapset_info = get_beatmap_info(&format!("https://osu.ppy.sh{beatmapset_url}"))?;
 
         let beatmapset_id = beatmapset_info
             .beatmapset_id
             .ok_or("Failed to get beatmapset ID in notify_beatmap_update")?;
 
+        let previous_beatmaps = if matches!(event_type, EventType::BeatmapsetUpdate { .. }) {
+            Some(beatmaps::get_beatmapset(connection, beatmapset_id).await?)
+        } else {
+            None
+        };
+
         sleep(Duration::from_secs(45)).await;
 
-        get_updated_beatmapset(connection, self.osu_client.clone(), beatmapset_id as u32).await
+        Ok((
+            previous_beatmaps,
+            get_updated_beatmapset(connection, self.osu_client.clone(), beatmapset_id as u32)
+                .await?,
+        ))
     }
 
     async fn notify_recent(
@@ -220,121 +275,43 @@ impl OsuTracker {
 
                     notified = true;
                 }
-                EventType::BeatmapsetApprove {
-                    approval,
-                    beatmapset,
-                    user: _user,
-                } => {
-                    if last_notifications.last_event.timestamp() > event.created_at.unix_timestamp()
-                    {
-                        continue;
-                    }
-
-                    let beatmapset = self
-                        .get_notify_beatmapset(connection, &beatmapset.url)
-                        .await?;
-
-                    let mut status = format!(
-                        "[**{} - {}**]({}) by [**{}**]({}) ",
-                        beatmapset.0.artist,
-                        beatmapset.0.title,
-                        format_beatmap_link(None, beatmapset.0.id, None),
-                        new.username,
-                        format_user_link(new.id),
-                    );
-
-                    match approval {
-                        RankStatus::Ranked | RankStatus::Approved => {
-                            status.push_str("has been ranked!")
-                        }
-                        RankStatus::Qualified => status.push_str("has been qualified!"),
-                        RankStatus::Loved => status.push_str("has been loved!"),
-                        _ => {}
-                    }
-
-                    self.notify_beatmap_update(beatmapset, connection, linked_profile, &status)
-                        .await?;
-
-                    notified = true;
-                }
-                EventType::BeatmapsetRevive {
-                    beatmapset,
-                    user: _user,
-                } => {
-                    if last_notifications.last_event.timestamp() > event.created_at.unix_timestamp()
-                    {
-                        continue;
-                    }
-
-                    let beatmapset = self
-                        .get_notify_beatmapset(connection, &beatmapset.url)
-                        .await?;
-
-                    let status = format!(
-                        "[**{} - {}**]({}) has been revived from eternal slumber by [**{}**]({})",
-                        beatmapset.0.artist,
-                        beatmapset.0.title,
-                        format_beatmap_link(None, beatmapset.0.id, None),
-                        new.username,
-                        format_user_link(new.id),
-                    );
-
-                    self.notify_beatmap_update(beatmapset, connection, linked_profile, &status)
-                        .await?;
-
-                    notified = true;
-                }
-                EventType::BeatmapsetUpdate {
-                    beatmapset,
-                    user: _user,
-                } => {
+                EventType::BeatmapsetApprove { beatmapset, .. }
+                | EventType::BeatmapsetRevive { beatmapset, .. }
+                | EventType::BeatmapsetUpdate { beatmapset, .. }
+                | EventType::BeatmapsetUpload { beatmapset, .. } => {
                     if last_notifications.last_event.timestamp() > event.created_at.unix_timestamp()
                     {
                         continue;
                     }
 
-                    let beatmapset = self
-                        .get_notify_beatmapset(connection, &beatmapset.url)
-                        .await?;
-
-                    let status = format!(
-                        "[**{}**]({}) has updated the beatmap [**{} - {}**]({})",
-                        new.username,
-                        format_user_link(new.id),
-                        beatmapset.0.artist,
-                        beatmapset.0.title,
-                        format_beatmap_link(None, beatmapset.0.id, None)
-                    );
-
-                    self.notify_beatmap_update(beatmapset, connection, linked_profile, &status)
+                    let (previous_beatmaps, beatmapset) = self
+                        .get_notify_beatmapset(connection, &beatmapset.url, &event.event_type)
                         .await?;
 
-                    notified = true;
-                }
-                EventType::BeatmapsetUpload {
-                    beatmapset,
-                    user: _user,
-                } => {
-                    if last_notifications.last_event.timestamp() > event.created_at.unix_timestamp()
-                    {
+                    let Some(status) = format_mapping_status(
+                        &event.event_type,
+                        &beatmapset.0,
+                        &new.username,
+                        new.id,
+                    ) else {
                         continue;
-                    }
-
-                    let beatmapset = self
-                        .get_notify_beatmapset(connection, &beatmapset.url)
-                        .await?;
+                    };
 
-                    let status = format!(
-                        "[**{}**]({}) has submitted a new beatmap [**{} - {}**]({})",
-                        new.username,
-                        format_user_link(new.id),
-                        beatmapset.0.artist,
-                        beatmapset.0.title,
-                        format_beatmap_link(None, beatmapset.0.id, None)
-                    );
-
-                    self.notify_beatmap_update(beatmapset, connection, linked_profile, &status)
-                        .await?;
+                    let changes = match previous_beatmaps {
+                        Some(previous_beatmaps) => {
+                            format_beatmapset_diff(&previous_beatmaps, &beatmapset.1)?
+                        }
+                        None => None,
+                    };
+
+                    self.notify_beatmap_update(
+                        beatmapset,
+                        connection,
+                        linked_profile,
+                        &status,
+                        changes,
+                    )
+                    .await?;
 
                     notified = true;
                 }
@@ -365,32 +342,46 @@ impl OsuTracker {
         connection: &mut AsyncPgConnection,
         linked_profile: &LinkedOsuProfile,
         status: &str,
+        changes: Option<String>,
     ) -> Result<(), Error> {
-        let mut embed = CreateEmbed::new();
+        let Some(mut embed) = create_beatmapset_embed(beatmapset, status)? else {
+            return Ok(());
+        };
 
-        let description = match beatmapset.1.len().cmp(&1) {
-            Ordering::Less => {
-                return Ok(());
-            }
-            Ordering::Equal => {
-                format!(
-                    "{}\n{}",
-                    status,
-                    format_single_beatmap(beatmapset.1.first().ok_or("Failed to get beatmapset")?)?
-                )
-            }
-            Ordering::Greater => {
-                format!("{}\n{}", status, format_beatmapset(beatmapset.1)?)
+        if let Some(changes) = changes {
+            embed = embed.field("Changes", changes, false);
+        }
+
+        let preference = osu_notification_preferences::read(connection, linked_profile.id).await?;
+
+        if preference
+            .as_ref()
+            .is_some_and(|preference| preference.direct_message)
+        {
+            if let Err(why) = UserId::new(u64::try_from(linked_profile.id)?)
+                .direct_message(&self.http, CreateMessage::new().embed(embed))
+                .await
+            {
+                error!(
+                    "Failed to send notification to user {}: {}",
+                    linked_profile.id, why
+                );
             }
-        };
 
-        embed = embed
-            .image(beatmapset.0.cover, Some("The beatmapset cover".into()))
-            .description(description);
+            return Ok(());
+        }
 
         for guild_id in self.cache.guilds() {
-            if let Ok(guild_channels) =
-                osu_guild_channels::read(connection, i64::try_from(guild_id.get())?).await
+            let db_guild_id = i64::try_from(guild_id.get())?;
+            if preference
+                .as_ref()
+                .is_some_and(|preference| preference.is_muted(db_guild_id))
+            {
+                continue;
+            }
+
+            if let Ok(guild_channels) = osu_guild_channels::read(connection, db_guild_id).await
+                && (linked_profile.verified || !guild_channels.require_verified)
                 && let Some(score_channels) = &guild_channels.score_channel
             {
                 for score_channel in score_channels
@@ -419,6 +410,109 @@ impl OsuTracker {
 
         Ok(())
     }
+    async fn notify_followed_mappers(
+        &mut self,
+        connection: &mut AsyncPgConnection,
+    ) -> Result<(), Error> {
+        let mut follows_by_mapper: HashMap<i64, Vec<FollowedMapper>> = HashMap::new();
+        for follow in followed_mappers::get_all(connection).await? {
+            follows_by_mapper
+                .entry(follow.mapper_id)
+                .or_default()
+                .push(follow);
+        }
+
+        for (mapper_id, follows) in follows_by_mapper {
+            let Some(oldest_check) = follows.iter().map(|follow| follow.last_event).min() else {
+                continue;
+            };
+            let checked_at = Utc::now();
+
+            let mut recent_events = match self
+                .osu_client
+                .recent_activity(u32::try_from(mapper_id)?)
+                .await
+            {
+                Ok(recent_events) => recent_events,
+                Err(why) => {
+                    error!(
+                        "Failed to get recent activity of mapper {}: {}",
+                        mapper_id, why
+                    );
+                    continue;
+                }
+            };
+            recent_events.reverse();
+
+            for event in &recent_events {
+                let (EventType::BeatmapsetApprove { beatmapset, .. }
+                | EventType::BeatmapsetRevive { beatmapset, .. }
+                | EventType::BeatmapsetUpdate { beatmapset, .. }
+                | EventType::BeatmapsetUpload { beatmapset, .. }) = &event.event_type
+                else {
+                    continue;
+                };
+
+                if oldest_check.timestamp() > event.created_at.unix_timestamp() {
+                    continue;
+                }
+
+                let beatmapset_id =
+                    match get_beatmap_info(&format!("https://osu.ppy.sh{}", beatmapset.url))
+                        .map(|info| info.beatmapset_id)
+                    {
+                        Ok(Some(beatmapset_id)) => beatmapset_id,
+                        _ => {
+                            error!(
+                                "Failed to get beatmapset ID of followed mapper event: {}",
+                                beatmapset.url
+                            );
+                            continue;
+                        }
+                    };
+
+                let mapper_event = FollowedMapperEvent {
+                    event_type: event.event_type.clone(),
+                    beatmapset_id,
+                    username: follows[0].username.clone(),
+                    mapper_id,
+                    guild_ids: follows
+                        .iter()
+                        .filter(|follow| {
+                            follow.last_event.timestamp() <= event.created_at.unix_timestamp()
+                        })
+                        .map(|follow| follow.guild_id)
+                        .collect(),
+                };
+
+                // Waiting for osu! to update the beatmapset shouldn't hold up the tracking loop.
+                let http = self.http.clone();
+                let osu_client = self.osu_client.clone();
+                let pool = self.pool.clone();
+                tokio::spawn(async move {
+                    if let Err(why) = mapper_event.notify(http, osu_client, pool).await {
+                        error!("Failed to post followed mapper event: {}", why);
+                    }
+                });
+            }
+
+            for follow in &follows {
+                if let Err(why) = followed_mappers::update_last_event(
+                    connection,
+                    follow.guild_id,
+                    mapper_id,
+                    checked_at,
+                )
+                .await
+                {
+                    error!("Failed to update followed mapper {}: {}", mapper_id, why);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     async fn notify_leaderboard_score(
         &mut self,
         beatmap: &EventBeatmap,
@@ -516,17 +610,72 @@ impl OsuTracker {
         let title_url =
             format_beatmap_link(Some(beatmap.0.id), beatmap.1.id, Some(&mode.to_string()));
 
+        let rule_check = ScoreRuleCheck {
+            mode: *mode,
+            pp: pp.pp,
+            position: None,
+            ranked: matches!(beatmap.0.status.as_str(), "Ranked" | "Approved"),
+        };
+
+        let preference = osu_notification_preferences::read(connection, linked_profile.id).await?;
+
+        if preference
+            .as_ref()
+            .is_some_and(|preference| preference.direct_message)
+        {
+            let embed = create_embed(
+                BLUE,
+                thumbnail,
+                &formatted_score,
+                &footer,
+                &new.avatar_url,
+                &author_text,
+                &user_link,
+                Some(title),
+                Some(title_url),
+            );
+
+            if let Err(why) = UserId::new(u64::try_from(linked_profile.id)?)
+                .direct_message(&self.http, CreateMessage::new().embed(embed))
+                .await
+            {
+                error!(
+                    "Failed to send notification to user {}: {}",
+                    linked_profile.id, why
+                );
+            }
+
+            return Ok(());
+        }
+
         for guild_id in self.cache.guilds() {
-            if let Ok(guild_channels) =
-                osu_guild_channels::read(connection, i64::try_from(guild_id.get())?).await
+            let db_guild_id = i64::try_from(guild_id.get())?;
+            if preference
+                .as_ref()
+                .is_some_and(|preference| preference.is_muted(db_guild_id))
+            {
+                continue;
+            }
+
+            if let Ok(guild_channels) = osu_guild_channels::read(connection, db_guild_id).await
+                && (linked_profile.verified || !guild_channels.require_verified)
                 && let Some(score_channels) = &guild_channels.score_channel
             {
+                let rules = osu_score_rules::get_guild(connection, guild_channels.guild_id).await?;
+
                 for score_channel in score_channels
                     .iter()
                     .flatten()
                     .copied()
                     .collect::<Vec<i64>>()
                 {
+                    if rules
+                        .iter()
+                        .any(|rule| rule.channel_id == score_channel && !rule.allows(&rule_check))
+                    {
+                        continue;
+                    }
+
                     if let Ok(member) = guild_id
                         .member(
                             (Some(&self.cache), self.http.http()),
@@ -560,3 +709,148 @@ impl OsuTracker {
         Ok(())
     }
 }
+
+struct FollowedMapperEvent {
+    event_type: EventType,
+    beatmapset_id: i64,
+    username: String,
+    mapper_id: i64,
+    guild_ids: Vec<i64>,
+}
+
+impl FollowedMapperEvent {
+    async fn notify(
+        self,
+        http: Arc<Http>,
+        osu_client: Arc<Osu>,
+        pool: Pool<AsyncDieselConnectionManager<AsyncPgConnection>>,
+    ) -> Result<(), Error> {
+        sleep(Duration::from_secs(45)).await;
+
+        let connection = &mut pool.get().await?;
+
+        let beatmapset =
+            get_updated_beatmapset(connection, osu_client, u32::try_from(self.beatmapset_id)?)
+                .await?;
+
+        let Some(status) = format_mapping_status(
+            &self.event_type,
+            &beatmapset.0,
+            &self.username,
+            self.mapper_id,
+        ) else {
+            return Ok(());
+        };
+
+        let Some(embed) = create_beatmapset_embed(beatmapset, &status)? else {
+            return Ok(());
+        };
+
+        for guild_id in self.guild_ids {
+            let Ok(guild_channels) = osu_guild_channels::read(connection, guild_id).await else {
+                continue;
+            };
+
+            for map_channel in guild_channels
+                .map_channel
+                .iter()
+                .flatten()
+                .flatten()
+                .copied()
+                .collect::<Vec<i64>>()
+            {
+                let builder = CreateMessage::new().embed(embed.clone());
+
+                if let Err(why) = GenericChannelId::from(u64::try_from(map_channel)?)
+                    .send_message(&http, builder)
+                    .await
+                {
+                    error!("Failed to post followed mapper event: {}", why);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn create_beatmapset_embed(
+    beatmapset: (Beatmapset, Vec<(Beatmap, OsuFile)>),
+    status: &str,
+) -> Result<Option<CreateEmbed<'static>>, Error> {
+    let description = match beatmapset.1.len().cmp(&1) {
+        Ordering::Less => {
+            return Ok(None);
+        }
+        Ordering::Equal => {
+            format!(
+                "{}\n{}",
+                status,
+                format_single_beatmap(beatmapset.1.first().ok_or("Failed to get beatmapset")?)?
+            )
+        }
+        Ordering::Greater => {
+            format!("{}\n{}", status, format_beatmapset(beatmapset.1)?)
+        }
+    };
+
+    Ok(Some(
+        CreateEmbed::new()
+            .image(beatmapset.0.cover, Some("The beatmapset cover".into()))
+            .description(description),
+    ))
+}
+
+fn format_mapping_status(
+    event_type: &EventType,
+    beatmapset: &Beatmapset,
+    username: &str,
+    user_id: i64,
+) -> Option<String> {
+    match event_type {
+        EventType::BeatmapsetApprove { approval, .. } => {
+            let mut status = format!(
+                "[**{} - {}**]({}) by [**{}**]({}) ",
+                beatmapset.artist,
+                beatmapset.title,
+                format_beatmap_link(None, beatmapset.id, None),
+                username,
+                format_user_link(user_id),
+            );
+
+            match approval {
+                RankStatus::Ranked | RankStatus::Approved => status.push_str("has been ranked!"),
+                RankStatus::Qualified => status.push_str("has been qualified!"),
+                RankStatus::Loved => status.push_str("has been loved!"),
+                _ => {}
+            }
+
+            Some(status)
+        }
+        EventType::BeatmapsetRevive { .. } => Some(format!(
+            "[**{} - {}**]({}) has been revived from eternal slumber by [**{}**]({})",
+            beatmapset.artist,
+            beatmapset.title,
+            format_beatmap_link(None, beatmapset.id, None),
+            username,
+            format_user_link(user_id),
+        )),
+        EventType::BeatmapsetUpdate { .. } => Some(format!(
+            "[**{}**]({}) has updated the beatmap [**{} - {}**]({})",
+            username,
+            format_user_link(user_id),
+            beatmapset.artist,
+            beatmapset.title,
+            format_beatmap_link(None, beatmapset.id, None)
+        )),
+        EventType::BeatmapsetUpload { .. } => Some(format!(
+            "[**{}**]({}) has submitted a new beatmap [**{} - {}**]({})",
+            username,
+            format_user_link(user_id),
+            beatmapset.artist,
+            beatmapset.title,
+            format_beatmap_link(None, beatmapset.id, None)
+        )),
+        _ => None,
+    }
+}
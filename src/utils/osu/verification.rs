@@ -0,0 +1,345 @@
+use crate::utils::db::linked_osu_profiles;
+use crate::{Error, Pool};
+use chrono::{DateTime, Duration, Utc};
+use dashmap::DashMap;
+use diesel_async::AsyncPgConnection;
+use diesel_async::pooled_connection::AsyncDieselConnectionManager;
+use rand::distr::{Alphanumeric, SampleString};
+use serde::Deserialize;
+use std::env;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::{Arc, LazyLock};
+use std::time::Duration as StdDuration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::Semaphore;
+use tokio::time::timeout;
+use tracing::{error, info};
+use url::Url;
+
+// How long an authorize URL stays valid for.
+const VERIFICATION_MINUTES: i64 = 10;
+const MAX_REQUEST_SIZE: usize = 8192;
+const MAX_CONNECTIONS: usize = 32;
+const REQUEST_TIMEOUT: StdDuration = StdDuration::from_secs(10);
+
+static REDIRECT_URI: LazyLock<Option<String>> = LazyLock::new(|| env::var("OSU_REDIRECT_URI").ok());
+
+static CALLBACK_PORT: LazyLock<u16> = LazyLock::new(|| {
+    env::var("OSU_VERIFY_PORT")
+        .unwrap_or_else(|_| String::from("9327"))
+        .parse::<u16>()
+        .expect("Failed to parse verification callback port.")
+});
+
+static PENDING_VERIFICATIONS: LazyLock<DashMap<String, PendingVerification>> =
+    LazyLock::new(DashMap::new);
+
+// Logins waiting for the user to confirm which Discord account they're verifying for.
+static PENDING_CONFIRMATIONS: LazyLock<DashMap<String, PendingConfirmation>> =
+    LazyLock::new(DashMap::new);
+
+struct PendingVerification {
+    discord_id: i64,
+    discord_name: String,
+    created_at: DateTime<Utc>,
+}
+
+impl PendingVerification {
+    fn is_expired(&self) -> bool {
+        Utc::now() - self.created_at > Duration::minutes(VERIFICATION_MINUTES)
+    }
+}
+
+struct PendingConfirmation {
+    discord_id: i64,
+    osu_id: i64,
+    username: String,
+    created_at: DateTime<Utc>,
+}
+
+impl PendingConfirmation {
+    fn is_expired(&self) -> bool {
+        Utc::now() - self.created_at > Duration::minutes(VERIFICATION_MINUTES)
+    }
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+}
+
+#[derive(Deserialize)]
+struct OwnUser {
+    id: i64,
+    username: String,
+}
+
+/// Verification is only available when the bot knows where osu! should send users back to.
+pub fn is_enabled() -> bool {
+    REDIRECT_URI.is_some()
+}
+
+/// Creates a one-time osu! authorize URL. Whichever account is authorized with it gets verified
+/// for `discord_id` once the user confirms it on the callback page, as long as it's linked to them.
+pub fn create_authorize_url(discord_id: i64, discord_name: &str) -> Result<String, Error> {
+    let redirect_uri = REDIRECT_URI
+        .as_deref()
+        .ok_or("Verification isn't set up in create_authorize_url")?;
+
+    PENDING_VERIFICATIONS.retain(|_, pending| !pending.is_expired());
+
+    let state = Alphanumeric.sample_string(&mut rand::rng(), 32);
+    PENDING_VERIFICATIONS.insert(
+        state.clone(),
+        PendingVerification {
+            discord_id,
+            discord_name: discord_name.to_string(),
+            created_at: Utc::now(),
+        },
+    );
+
+    let mut url = Url::parse("https://osu.ppy.sh/oauth/authorize")?;
+    url.query_pairs_mut()
+        .append_pair("client_id", &env::var("OSU_CLIENT_ID")?)
+        .append_pair("redirect_uri", redirect_uri)
+        .append_pair("response_type", "code")
+        .append_pair("scope", "identify")
+        .append_pair("state", &state);
+
+    Ok(url.to_string())
+}
+
+#[derive(Clone)]
+pub struct VerificationServer {
+    pub http_client: reqwest::Client,
+    pub pool: Pool<AsyncDieselConnectionManager<AsyncPgConnection>>,
+}
+
+impl VerificationServer {
+    pub async fn listen(&self) -> Result<(), Error> {
+        let listener = TcpListener::bind(SocketAddr::new(
+            IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+            *CALLBACK_PORT,
+        ))
+        .await?;
+        info!(
+            "Listening for osu! verification callbacks on port {}",
+            *CALLBACK_PORT
+        );
+
+        let connections = Arc::new(Semaphore::new(MAX_CONNECTIONS));
+
+        loop {
+            let (stream, _) = listener.accept().await?;
+
+            // Connections past the limit are dropped instead of queued.
+            let Ok(permit) = connections.clone().try_acquire_owned() else {
+                continue;
+            };
+
+            let server = self.clone();
+            tokio::spawn(async move {
+                if let Err(why) = server.handle_connection(stream).await {
+                    error!(
+                        "Error occurred while handling verification callback: {}",
+                        why
+                    );
+                }
+                drop(permit);
+            });
+        }
+    }
+
+    async fn handle_connection(&self, mut stream: TcpStream) -> Result<(), Error> {
+        let Ok(request) = timeout(REQUEST_TIMEOUT, read_request(&mut stream)).await else {
+            return Ok(());
+        };
+        let request = request?;
+
+        // Only the request line matters, e.g. `GET /callback?code=..&state=.. HTTP/1.1`.
+        let request = String::from_utf8_lossy(&request);
+        let mut request_line = request
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .split_whitespace();
+        let (status, body) = match (request_line.next(), request_line.next()) {
+            (Some(method @ ("GET" | "POST")), Some(path)) => {
+                let url = Url::parse(&format!("http://localhost{path}"))?;
+                let query = |name: &str| {
+                    url.query_pairs()
+                        .find(|(key, _)| key == name)
+                        .map(|(_, value)| value.into_owned())
+                };
+
+                let result = match (method, url.path()) {
+                    ("POST", path) if path.ends_with("/confirm") => match query("token") {
+                        Some(token) => Some(self.confirm(&token).await),
+                        None => None,
+                    },
+                    ("GET", _) => match (query("code"), query("state")) {
+                        (Some(code), Some(state)) => Some(self.verify(&code, &state).await),
+                        _ => None,
+                    },
+                    _ => None,
+                };
+
+                match result {
+                    Some(Ok(message)) => ("200 OK", message),
+                    Some(Err(why)) => {
+                        error!("Failed to verify osu! account: {}", why);
+                        (
+                            "500 Internal Server Error",
+                            page(
+                                "Something went wrong while verifying your account. Try again with a new link.",
+                            ),
+                        )
+                    }
+                    None => ("404 Not Found", page("Not found.")),
+                }
+            }
+            _ => ("404 Not Found", page("Not found.")),
+        };
+
+        let response = format!(
+            "HTTP/1.1 {status}\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        );
+        stream.write_all(response.as_bytes()).await?;
+        stream.shutdown().await?;
+
+        Ok(())
+    }
+
+    /// Logs in with the code osu! sent back, and asks the user to confirm the Discord account the
+    /// osu! account is verified for before anything is changed.
+    async fn verify(&self, code: &str, state: &str) -> Result<String, Error> {
+        let Some((_, pending)) = PENDING_VERIFICATIONS.remove(state) else {
+            return Ok(page(
+                "This link has already been used. Run the verify command again for a new one.",
+            ));
+        };
+
+        if pending.is_expired() {
+            return Ok(page(
+                "This link has expired. Run the verify command again for a new one.",
+            ));
+        }
+
+        let redirect_uri = REDIRECT_URI
+            .as_deref()
+            .ok_or("Verification isn't set up in verify")?;
+
+        let token_response = self
+            .http_client
+            .post("https://osu.ppy.sh/oauth/token")
+            .form(&[
+                ("client_id", env::var("OSU_CLIENT_ID")?.as_str()),
+                ("client_secret", env::var("OSU_CLIENT_SECRET")?.as_str()),
+                ("code", code),
+                ("grant_type", "authorization_code"),
+                ("redirect_uri", redirect_uri),
+            ])
+            .send()
+            .await?
+            .error_for_status()?
+            .bytes()
+            .await?;
+        let token: TokenResponse = serde_json::from_slice(&token_response)?;
+
+        let user_response = self
+            .http_client
+            .get("https://osu.ppy.sh/api/v2/me")
+            .bearer_auth(token.access_token)
+            .send()
+            .await?
+            .error_for_status()?
+            .bytes()
+            .await?;
+        let user: OwnUser = serde_json::from_slice(&user_response)?;
+
+        let connection = &mut self.pool.get().await?;
+        if linked_osu_profiles::read_account(connection, pending.discord_id, user.id)
+            .await
+            .is_err()
+        {
+            return Ok(page(&format!(
+                "{} isn't linked to the Discord account {}. Link it with the link command first, \
+                 then run the verify command again.",
+                user.username, pending.discord_name
+            )));
+        }
+
+        PENDING_CONFIRMATIONS.retain(|_, pending| !pending.is_expired());
+
+        let confirmation_token = Alphanumeric.sample_string(&mut rand::rng(), 32);
+        PENDING_CONFIRMATIONS.insert(
+            confirmation_token.clone(),
+            PendingConfirmation {
+                discord_id: pending.discord_id,
+                osu_id: user.id,
+                username: user.username.clone(),
+                created_at: Utc::now(),
+            },
+        );
+
+        Ok(format!(
+            "<!DOCTYPE html><html><body><p>You're verifying the osu! account <b>{}</b> for the \
+             Discord account <b>{}</b>. Only continue if that's your Discord account.</p>\
+             <form method=\"post\" action=\"confirm?token={confirmation_token}\">\
+             <button type=\"submit\">Verify</button></form></body></html>",
+            escape_html(&user.username),
+            escape_html(&pending.discord_name)
+        ))
+    }
+
+    async fn confirm(&self, token: &str) -> Result<String, Error> {
+        let Some((_, pending)) = PENDING_CONFIRMATIONS.remove(token) else {
+            return Ok(page(
+                "This link has already been used. Run the verify command again for a new one.",
+            ));
+        };
+
+        if pending.is_expired() {
+            return Ok(page(
+                "This link has expired. Run the verify command again for a new one.",
+            ));
+        }
+
+        let connection = &mut self.pool.get().await?;
+        linked_osu_profiles::set_verified(connection, pending.discord_id, pending.osu_id).await?;
+
+        Ok(page(&format!(
+            "Verified {}! You can close this page now.",
+            pending.username
+        )))
+    }
+}
+
+async fn read_request(stream: &mut TcpStream) -> Result<Vec<u8>, Error> {
+    let mut request = Vec::new();
+    let mut buffer = [0; 1024];
+    while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+        let read = stream.read(&mut buffer).await?;
+        if read == 0 || request.len() + read > MAX_REQUEST_SIZE {
+            break;
+        }
+        request.extend_from_slice(&buffer[..read]);
+    }
+    Ok(request)
+}
+
+fn page(message: &str) -> String {
+    format!(
+        "<!DOCTYPE html><html><body><p>{}</p></body></html>",
+        escape_html(message)
+    )
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}